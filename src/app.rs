@@ -0,0 +1,3759 @@
+use std::collections::{BTreeSet, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use anyhow::{Context, Result};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers, MouseEvent, MouseEventKind};
+use ratatui::layout::Rect;
+use ratatui::style::Color;
+use ratatui::widgets::{Row, TableState};
+
+use crate::clipboard::ClipboardContext;
+use crate::config::{ColumnWidthConfig, Preset, TzOption};
+use crate::display::{self, DisplayData};
+use crate::expr::FilterExpr;
+use crate::filter::{DisplayMode, Filter, TagFilter};
+use crate::format::LogFormat;
+use crate::histogram::{self, Bucket};
+use crate::loader::{self, Loader};
+use crate::log_entry::{LogEntry, LogLevel};
+use crate::search;
+use crate::search_worker::{self, SearchWorker};
+use crate::state::{SortColumn, SortDirection, State};
+use crate::stats::{self, TagStat};
+use crate::styles;
+use crate::text_utils::create_text;
+use crate::ui::{ColumnWidths, LEVEL_COLUMN_WIDTH, TID_COLUMN_WIDTH};
+
+/// `L` cycles the minimum-level filter through this sequence, wrapping back
+/// to `None` (no filter) after `Fatal`.
+const LEVEL_CYCLE: [Option<LogLevel>; 7] = [
+    None,
+    Some(LogLevel::Verbose),
+    Some(LogLevel::Debug),
+    Some(LogLevel::Info),
+    Some(LogLevel::Warn),
+    Some(LogLevel::Error),
+    Some(LogLevel::Fatal),
+];
+
+/// `s` cycles the sort column through this sequence, wrapping back to `None`
+/// (load order) after `Tag`; see [`App::cycle_sort_column`].
+const SORT_CYCLE: [Option<SortColumn>; 6] = [
+    None,
+    Some(SortColumn::Timestamp),
+    Some(SortColumn::Pid),
+    Some(SortColumn::Tid),
+    Some(SortColumn::Level),
+    Some(SortColumn::Tag),
+];
+
+/// Which input the keyboard is currently feeding.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub enum Mode {
+    #[default]
+    Normal,
+    /// Typing a `:`-prefixed command, e.g. `:filter-tag ~ActivityManag`.
+    Command,
+    /// Typing a tag filter opened with `t` (include) or `T` (exclude);
+    /// live-updates as you type and discards the in-progress pattern (but
+    /// not any already stacked) on `Esc`.
+    TagFilter,
+    /// Typing a free-form filter expression opened with `f`; see
+    /// [`crate::expr::FilterExpr`]. Applies after a short debounce rather
+    /// than on every keystroke, and discards the edit (reverting to the
+    /// last committed expression) on `Esc`.
+    FilterExpr,
+    /// Typing a quick search opened with `/`; live-updates `state.quick_search`
+    /// as you type, like the tag filter prompt. `Enter` commits and leaves it
+    /// set; `Esc` puts the pattern and selection back as they were before the
+    /// prompt opened. Prefix the pattern with `tag:`,
+    /// `msg:`, `pid:`, or `tid:` to restrict matching to that column instead
+    /// of tag-or-message; see [`crate::search::parse_pattern`].
+    QuickSearch,
+    /// Browsing the `F` preset popup with `Up`/`Down`; `Enter` activates the
+    /// highlighted preset, `Esc` closes the popup without changing anything.
+    PresetPicker,
+    /// Waiting for a single key after `Ctrl+X`: `p`/`t`/`l`/`m` create an
+    /// include filter from the selected row's PID/tag/level/first message
+    /// word, and their Shift'd forms create an exclude filter instead. Any
+    /// other key cancels without creating one. See [`App::quick_filter_input`].
+    QuickFilter,
+    /// Browsing the `Alt+S` tag-stats popup with `Up`/`Down`; `Enter`
+    /// filters the table down to the highlighted tag and closes the popup,
+    /// `Esc` closes it without changing anything. See [`App::open_tag_stats`].
+    TagStats,
+    /// Browsing the `Alt+H` volume-over-time histogram with `Left`/`Right`;
+    /// `Enter` jumps the table to the highlighted bucket's earliest entry
+    /// and closes the popup, `Esc` closes it without changing anything. See
+    /// [`App::open_histogram`].
+    Histogram,
+    /// Browsing the `Alt+P` pinned-highlights popup with `Up`/`Down`;
+    /// `Enter`/`Delete` unpins the highlighted pattern, `Esc` closes the
+    /// popup without changing anything. See [`App::open_pinned_highlights`].
+    PinnedHighlights,
+    /// Browsing the `M` bookmarks popup with `Up`/`Down`; `Enter` jumps the
+    /// table to the highlighted bookmark and closes the popup, `Delete`/`d`
+    /// removes it, `Esc` closes the popup without changing anything. See
+    /// [`App::open_bookmarks`].
+    Bookmarks,
+    /// Viewing the `Space`/`o` detail popup for the selected row, every
+    /// field on its own line and the message word-wrapped with internal
+    /// `Up`/`Down` scrolling; the same key or `Esc` closes it. See
+    /// [`App::open_entry_detail`].
+    EntryDetail,
+}
+
+/// Row field the `Ctrl+X` quick filter prompt can build an include/exclude
+/// filter from; see [`App::quick_filter_input`].
+#[derive(Debug, Clone, Copy)]
+enum QuickFilterField {
+    Pid,
+    Tag,
+    Level,
+    Message,
+}
+
+/// Which widget `Tab`/`Ctrl+I` moves keyboard focus to, for mouse-less
+/// navigation. `SearchBar` and `Sidebar` are placeholders: this codebase
+/// doesn't have a persistent search bar or tag sidebar yet (quick search is
+/// a transient `/` prompt in the status bar, not a focusable widget, and
+/// there's no sidebar at all), so
+/// [`App::cycle_focus`] can't actually reach them until those widgets exist.
+/// The enum and the `Escape`-returns-to-`Table` convention are in place now
+/// so wiring them in later is additive.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum FocusedWidget {
+    #[default]
+    Table,
+    // Not constructed anywhere yet — reserved for when a persistent search
+    // bar and tag sidebar exist to focus.
+    #[allow(dead_code)]
+    SearchBar,
+    #[allow(dead_code)]
+    Sidebar,
+}
+
+/// Which column of the table a terminal x-coordinate falls in, used to
+/// target a triple-click at the right filter command.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TableColumn {
+    Timestamp,
+    /// The `Alt+T` inter-line time delta column; only present in
+    /// [`App::column_at`]'s layout when [`App::show_delta_column`] is set.
+    Delta,
+    Pid,
+    Tid,
+    Level,
+    Tag,
+    Message,
+}
+
+/// Selection state for the main log table.
+pub struct LogTable {
+    pub state: TableState,
+}
+
+impl LogTable {
+    pub fn new() -> Self {
+        let mut state = TableState::default();
+        state.select(Some(0));
+        LogTable { state }
+    }
+
+    pub fn selected(&self) -> Option<usize> {
+        self.state.selected()
+    }
+
+    pub fn select(&mut self, index: Option<usize>) {
+        self.state.select(index);
+    }
+}
+
+impl Default for LogTable {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Which indices into `entries` [`DisplayData`] should be built from: every
+/// row passing `state.filter`, or, when `matches_only` is set and a quick
+/// search is active, only the subset of those also matching it.
+fn display_indices(state: &State, matches_only: bool) -> &[usize] {
+    if matches_only && state.quick_search.is_some() {
+        &state.results
+    } else {
+        &state.filtered_indices
+    }
+}
+
+/// Load and parse a logcat dump from `path` using `format` (auto-detecting
+/// it from the first few lines if `format` is [`LogFormat::Auto`]), skipping
+/// lines that don't match rather than failing the whole load. Returns the
+/// entries along with the format actually used.
+///
+/// Memory-maps `path` instead of reading it into one big `String`, so a
+/// multi-gigabyte file doesn't need a matching allocation up front — the
+/// parsed entries end up owning just the substrings they need.
+///
+/// Also returns the encoding [`crate::encoding::decode`] used, so callers
+/// can surface a notice when it wasn't plain UTF-8.
+///
+/// `path` not existing or being unreadable is reported as a plain one-line
+/// message rather than an `io::Error`'s `Debug` dump; note there's no
+/// "invalid UTF-8" case to distinguish here, since [`crate::encoding::decode`]
+/// is best-effort and always falls back to a lossy or Latin-1 decode instead
+/// of failing.
+pub fn load_logfile(
+    path: &Path,
+    format: LogFormat,
+) -> Result<(Vec<LogEntry>, LogFormat, &'static str)> {
+    let file = fs::File::open(path).map_err(|err| match err.kind() {
+        std::io::ErrorKind::NotFound => {
+            anyhow::anyhow!("log file not found: {}", path.display())
+        }
+        std::io::ErrorKind::PermissionDenied => {
+            anyhow::anyhow!("permission denied reading log file: {}", path.display())
+        }
+        _ => {
+            anyhow::Error::from(err).context(format!("failed to open log file {}", path.display()))
+        }
+    })?;
+    if file.metadata().map(|m| m.len()).unwrap_or(0) == 0 {
+        let format = if format == LogFormat::Auto {
+            LogFormat::Threadtime
+        } else {
+            format
+        };
+        return Ok((Vec::new(), format, "UTF-8"));
+    }
+    // SAFETY: the mapping is read-only and scoped to this call; if `path` is
+    // truncated by another process while we're reading it we may see short
+    // reads or a SIGBUS, the same risk any mmap-based reader accepts.
+    let mmap = unsafe { memmap2::Mmap::map(&file) }
+        .with_context(|| format!("failed to memory-map log file {}", path.display()))?;
+    let (contents, encoding) = crate::encoding::decode(&mmap);
+    let format = match format {
+        LogFormat::Auto => LogFormat::detect(contents.lines()),
+        format => format,
+    };
+    let entries = contents
+        .lines()
+        .filter_map(|line| format.parse(line).ok())
+        .collect();
+    Ok((entries, format, encoding))
+}
+
+/// Like [`load_logfile`], but reads several files and interleaves their
+/// entries into one timestamp-ordered stream instead of returning just one
+/// file's; see `--merge`. Ties (entries sharing a timestamp) keep the order
+/// `paths` were given in, then each file's own order, via a stable sort.
+/// The second element of the result is each entry's index into `paths`, in
+/// the same order as the returned entries, for the `--merge` left gutter;
+/// see [`App::new_merged`].
+pub fn load_merged_logfiles(
+    paths: &[PathBuf],
+    format: LogFormat,
+) -> Result<(Vec<LogEntry>, Vec<usize>, &'static str)> {
+    let mut encoding = "UTF-8";
+    let mut tagged: Vec<(usize, LogEntry)> = Vec::new();
+    for (file_index, path) in paths.iter().enumerate() {
+        let (entries, _, file_encoding) = load_logfile(path, format)?;
+        if file_index == 0 {
+            encoding = file_encoding;
+        }
+        tagged.extend(entries.into_iter().map(|entry| (file_index, entry)));
+    }
+    tagged.sort_by(|(a_index, a), (b_index, b)| {
+        a.timestamp.cmp(&b.timestamp).then(a_index.cmp(b_index))
+    });
+
+    let mut entries = Vec::with_capacity(tagged.len());
+    let mut sources = Vec::with_capacity(tagged.len());
+    for (file_index, entry) in tagged {
+        sources.push(file_index);
+        entries.push(entry);
+    }
+    Ok((entries, sources, encoding))
+}
+
+/// Top-level application state driving the TUI.
+pub struct App {
+    pub path: PathBuf,
+    pub format: LogFormat,
+    pub entries: Vec<LogEntry>,
+    pub table: LogTable,
+    pub display: DisplayData,
+    pub state: State,
+    pub status: String,
+    pub should_quit: bool,
+    pub mode: Mode,
+    pub input_buffer: String,
+    /// Byte offset of the edit cursor within `input_buffer`, always on a
+    /// char boundary. Moved/rendered by the `input_*` helpers below and
+    /// [`crate::ui::draw`]; see [`App::set_input_buffer`].
+    pub input_cursor: usize,
+    /// Widget `Tab`/`Ctrl+I` currently targets; see [`FocusedWidget`].
+    pub focus: FocusedWidget,
+    /// When true, the table renders each row's original unparsed line in a
+    /// single column instead of the usual timestamp/pid/tid/level/tag/message
+    /// layout. Toggled with `Alt+R`.
+    pub show_raw: bool,
+    /// When true, an extra column after the timestamp shows the time elapsed
+    /// since the previous visible row, so stalls are visible without
+    /// scrolling through every timestamp by hand. Off by default since it
+    /// takes up space most sessions don't need. Toggled with `Alt+T`; see
+    /// [`App::toggle_delta_column`].
+    pub show_delta_column: bool,
+    /// Minimum gap for [`App::show_delta_column`]'s value to be painted with
+    /// [`crate::styles::delta_highlight_style`] instead of the plain row
+    /// color; defaults to one second, overridden with `--delta-threshold-ms`.
+    delta_highlight_threshold: chrono::Duration,
+    /// How many new rows have arrived from a streaming source (currently
+    /// only `journalctl --follow`; see [`App::journald`]) since the
+    /// selection last stopped tracking the latest line. Zero whenever the
+    /// selection is on the last row — [`App::drain_loader`] keeps it pinned
+    /// there as new entries come in — and only grows while the user has
+    /// scrolled up, so they know how much they'd catch up on by jumping to
+    /// the bottom (`G`/`End`); see [`App::new_lines_below`].
+    new_lines_below: usize,
+    /// When true and a quick search is active, `display` is built from only
+    /// the rows matching it (`state.results`) instead of every row passing
+    /// `state.filter`. Toggled with `m`; see [`App::toggle_matches_only`].
+    matches_only: bool,
+    /// When true, search-match highlighting is suppressed in the table even
+    /// though `state.quick_search`/`state.results` are still live — vim's
+    /// `:noh`. Toggled with `Ctrl-N`; see [`App::toggle_search_highlight`].
+    /// Cleared automatically by [`App::jump_to_match`] (`n`/`N`) and
+    /// [`App::run_incremental_search`] (`/` + `Enter`, `*`), so the
+    /// highlight comes back the moment the search is used again.
+    pub search_highlight_hidden: bool,
+    /// Whether the tag filter currently being typed at the `t`/`T` prompt is
+    /// an exclude filter (opened with `T`) or an include filter (`t`).
+    tag_filter_exclude: bool,
+    /// Tag filters already committed (via Enter or `:filter-tag`); the
+    /// in-progress `input_buffer` is layered on top of these live, and
+    /// folded in permanently on Enter. See [`App::apply_tag_filter_input`].
+    committed_tag_filters: Vec<TagFilter>,
+    /// Raw text of the last committed `f` filter expression, empty if none.
+    /// Prefills the bar when `f` is pressed again, and shown in the status
+    /// bar after committing.
+    filter_expr_text: String,
+    /// Parse error for the expression currently in `input_buffer` while in
+    /// [`Mode::FilterExpr`], shown inline in the bar.
+    pub filter_expr_error: Option<String>,
+    /// Named filter presets loaded from `~/.config/logcatui/filters.toml`,
+    /// in file order; `F` opens a popup to pick one. Empty if the file is
+    /// missing or has none.
+    pub presets: Vec<Preset>,
+    /// Index into `presets` currently highlighted in [`Mode::PresetPicker`].
+    preset_picker_selected: usize,
+    /// Name of the preset behind the currently active `state.filter.preset_expr`,
+    /// if any; shown in the status bar after activating one.
+    active_preset: Option<String>,
+    /// Per-tag entry counts shown in the `Alt+S` popup, recomputed each time
+    /// it's opened; see [`App::open_tag_stats`]. Empty outside
+    /// [`Mode::TagStats`].
+    tag_stats: Vec<TagStat>,
+    /// Index into `tag_stats` currently highlighted in [`Mode::TagStats`].
+    tag_stats_selected: usize,
+    /// Time-bucketed entry counts shown in the `Alt+H` popup, recomputed
+    /// each time it's opened; see [`App::open_histogram`]. Empty outside
+    /// [`Mode::Histogram`].
+    histogram_buckets: Vec<Bucket>,
+    /// Index into `histogram_buckets` currently highlighted in
+    /// [`Mode::Histogram`].
+    histogram_selected: usize,
+    /// Quick-search patterns pinned with `Ctrl-H`, each highlighted in its
+    /// own color from [`styles::pin_highlight_style`] (assigned by position
+    /// in this list) alongside the active `state.quick_search`, so two or
+    /// more terms can stay visible at once for correlating events. Only
+    /// `state.quick_search` participates in `n`/`N` navigation and
+    /// `matches_only` filtering — these are highlight-only, not a second
+    /// active search. See [`App::pin_current_search`].
+    pinned_highlights: Vec<String>,
+    /// Index into `pinned_highlights` currently highlighted in
+    /// [`Mode::PinnedHighlights`].
+    pinned_selected: usize,
+    /// Indices into `entries` toggled with `Ctrl-B`, for hopping between the
+    /// handful of lines worth returning to while reading a long log (e.g.
+    /// the first error, an ANR dump, a restart point). Keyed by entry index
+    /// rather than display row so a bookmark survives filtering — a hidden
+    /// bookmark is simply skipped by [`App::jump_to_bookmark`], not dropped.
+    /// See [`App::toggle_bookmark`] and [`App::open_bookmarks`].
+    bookmarks: BTreeSet<usize>,
+    /// Index into `bookmarks` (in order) currently highlighted in
+    /// [`Mode::Bookmarks`].
+    bookmark_selected: usize,
+    /// Lines scrolled down in the `Space`/`o` detail popup's message, reset
+    /// each time it's opened. See [`App::open_entry_detail`] and
+    /// [`App::entry_detail_input`].
+    detail_scroll: u16,
+    /// Text of the filter expression behind the currently active
+    /// `state.filter.quick_filter`, if any; shown in the status bar and
+    /// cleared by a second `Ctrl+X`. See [`App::quick_filter_input`].
+    quick_filter_text: Option<String>,
+    /// A successfully-parsed, not-yet-applied filter expression and when it
+    /// was typed; applied once `FILTER_EXPR_DEBOUNCE` has elapsed without a
+    /// further edit. See [`App::tick`].
+    pending_filter_expr: Option<(String, Instant)>,
+    /// `(pattern, typed-at)` for the `/` quick search prompt; re-run once
+    /// `QUICK_SEARCH_DEBOUNCE` has elapsed without a further keystroke, the
+    /// same debounce-then-apply shape as `pending_filter_expr`. See
+    /// [`App::tick`] and [`App::run_incremental_search`].
+    pending_quick_search: Option<(String, Instant)>,
+    /// `state.quick_search` and the selected row, captured when the `/`
+    /// prompt opens, so `Esc` can put both back exactly as they were before
+    /// any incremental typing moved them. `None` outside [`Mode::QuickSearch`].
+    quick_search_restore: Option<(Option<String>, Option<usize>)>,
+    /// Whether the in-progress `/` prompt was opened with `Alt+/` (search
+    /// forward from the current row, like `less`'s `/`) rather than plain
+    /// `/` (jump to the nearest match in either direction). Consulted by
+    /// [`App::run_incremental_search`] and [`App::poll_search_worker`] to
+    /// pick which of [`App::jump_to_first_match_from_here`] or
+    /// [`App::jump_to_nearest_match`] to land on once a pattern commits.
+    quick_search_from_here: bool,
+    /// Index into `state.search_history` currently shown in the `/` prompt
+    /// while browsing with `Up`/`Down`, or `None` when showing the
+    /// live-typed text. Reset whenever the prompt opens or closes.
+    search_history_cursor: Option<usize>,
+    /// `input_buffer` as it was before `Up` started browsing history, so
+    /// `Down` can restore it once the cursor reaches the bottom. `None`
+    /// outside a history browse.
+    search_history_draft: Option<String>,
+    /// How long the most recent incremental search took to re-filter
+    /// `entries`, shown next to the match count while typing. `None` before
+    /// any search has run this session.
+    last_search_elapsed: Option<Duration>,
+    /// Set whenever the model or an input changes; the render loop only
+    /// redraws while this is `true`, then clears it.
+    pub dirty: bool,
+    /// Screen area the table was last drawn into, used to translate mouse
+    /// clicks into row indices.
+    pub table_area: Rect,
+    /// Set by `Ctrl-P` to the selected line; `--print-on-exit` prints this
+    /// to stdout after leaving the alternate screen and exits 0, or exits 1
+    /// if the user quit with `q` without picking anything.
+    pub picked: Option<String>,
+    /// Set to briefly color the status bar background for tactile feedback,
+    /// e.g. landing on an `Error` row. See [`App::active_flash_color`].
+    status_flash: Option<(Color, Instant)>,
+    /// Resolved `tag`/`pid`/`timestamp` column widths; see [`ColumnWidthConfig`].
+    pub column_widths: ColumnWidths,
+    column_width_config: ColumnWidthConfig,
+    /// Interactive Tag-column width override set by `<`/`>`/`Alt+Left`/
+    /// `Alt+Right` (see [`App::adjust_tag_width`]); re-applied on top of
+    /// [`App::column_width_config`]'s own resolution whenever `column_widths`
+    /// is recomputed, so the override survives streaming appends and
+    /// reloads for the lifetime of the process. Not persisted across
+    /// restarts, like the other view toggles.
+    tag_width_override: Option<u16>,
+    /// `--tz`: how timestamps are displayed, set once at startup. Defaults
+    /// to [`TzOption::Utc`] (shown as stored, this crate's long-standing
+    /// behavior); see [`TzOption`] for what `Local`/`Fixed` actually mean
+    /// given these timestamps carry no recorded zone. Purely a display
+    /// concern — entries are still stored, sorted, and filtered on the
+    /// original, unshifted value.
+    display_tz: TzOption,
+    /// Row indices (into `entries`, not `display.rows`) toggled to a
+    /// collapsed, single-line rendering via `Enter` or a double-click.
+    collapsed_rows: HashSet<usize>,
+    /// Characters scrolled into a collapsed row's message by `h`/`l` or
+    /// `Shift-Left`/`Shift-Right`, applied in [`display::DisplayData::as_row`]
+    /// with an ellipsis on whichever side is clipped; a wrapped (uncollapsed)
+    /// row already shows its whole message across multiple lines, so this
+    /// has no effect there. One offset shared by the whole table, not
+    /// per-row, the same way [`App::zebra_striping`] or `show_delta_column`
+    /// are table-wide rather than per-row settings. See
+    /// [`App::scroll_message`].
+    message_scroll: usize,
+    /// The row and time of the most recent mouse-down, for double/triple
+    /// click detection.
+    last_click: Option<(usize, Instant)>,
+    /// How many consecutive clicks on the same row within `double_click_ms`
+    /// have been seen so far.
+    click_streak: u8,
+    double_click_ms: u64,
+    /// The background parse started in [`App::new`]; `Some` until fully
+    /// drained into `entries`, at which point `status` is set to the final
+    /// "Parsed N entries" message and this is cleared. See [`App::tick`].
+    loader: Option<Loader>,
+    started_at: Instant,
+    frames: u64,
+    /// True when this app is streaming from `journalctl --follow` (see
+    /// [`App::new_journald`]) rather than reading a file; disables `reload`,
+    /// which has no file on disk to re-read.
+    journald: bool,
+    /// Set by `--persist-session`: on quit, [`App::save_session`] writes the
+    /// scroll position, filters, and bookmarks out keyed by `path`, and
+    /// [`App::new`] restored them from there at startup if `path` hadn't
+    /// changed since. Off by default — most invocations are a one-off look
+    /// at a fresh dump, not a file revisited across sessions.
+    pub persist_session: bool,
+    /// The scroll position carried over from a previous session by
+    /// `--persist-session`, applied once [`App::drain_loader`] has loaded
+    /// enough of the file for it to resolve to a real row. `None` once
+    /// applied, or if `--persist-session` is off or nothing was restored.
+    pending_restore_selection: Option<usize>,
+    /// Set by [`App::new_merged`] (`--merge`) to the files that were merged
+    /// together, so `reload` can re-read and re-sort all of them instead of
+    /// just `path` (which is a synthetic `a.log + b.log`-style label in this
+    /// mode, not a real file). `None` outside of `--merge`.
+    merge_paths: Option<Vec<PathBuf>>,
+    /// `merge_sources[i]` is the index into `merge_paths` that `entries[i]`
+    /// came from, for the `--merge` left gutter colored by
+    /// [`crate::styles::pin_highlight_style`]; see [`App::build_row`].
+    /// Empty outside of `--merge`.
+    merge_sources: Vec<usize>,
+    /// Toggled with `Alt+D`; shows the FPS/timing segment of the status bar,
+    /// hidden by default to make room for [`App::filter_indicator`].
+    pub debug: bool,
+    /// Set by `--trace`: starts with `debug` already on, and reports how
+    /// many lines failed to parse once loading finishes (see
+    /// [`App::drain_loader`]). A developer/contributor mode for diagnosing a
+    /// dump that's behaving oddly, rather than something end users need day
+    /// to day.
+    pub trace: bool,
+    /// Toggled with `Alt+Z`; renders even/odd display rows with alternating
+    /// backgrounds (see [`crate::styles::zebra_style`]) to make dense logs
+    /// easier to scan. Off by default.
+    pub zebra_striping: bool,
+    /// Toggled with `Alt+G`; highlights the Tag cell of every row sharing
+    /// the selected row's tag (see [`crate::styles::tag_highlight_style`]),
+    /// so a tag's activity is easy to track across a busy log. Off by
+    /// default; purely presentational — doesn't filter or search. See
+    /// [`App::toggle_tag_highlight`].
+    pub tag_highlight_enabled: bool,
+    /// Toggled with `Alt+C`; swaps the row's full-strength
+    /// [`crate::styles::level_style`] tint for a dimmer
+    /// [`crate::styles::muted_level_style`] variant, for sessions where
+    /// coloring every cell (message text included) by log level reads as
+    /// too loud. Off by default, matching the full-strength tint this crate
+    /// has always shipped with. See [`App::toggle_muted_level_color`].
+    pub muted_level_color: bool,
+    /// The background scan started by the most recent
+    /// [`App::run_incremental_search`]; `Some` until fully drained into
+    /// `state.results`, at which point it's cleared. See [`App::tick`].
+    search_worker: Option<SearchWorker>,
+    /// Whether `n`/`N` wrap to the first/last quick-search match after
+    /// running off the end of the table, like vim's `wrapscan`. On by
+    /// default; toggled with `Alt+W`. See [`App::jump_to_match`].
+    pub search_wrap: bool,
+    /// Index into the selected row's search-match spans (as computed by
+    /// [`search::match_spans`]) that `n`/`N` most recently landed on, so a
+    /// line with several matches can be stepped through one at a time
+    /// before moving to the next line; see [`App::jump_to_match`] and
+    /// [`App::current_match_span`]. Reset to `0` whenever the selection
+    /// moves to a different row by any means other than that in-line
+    /// stepping.
+    current_match_span: usize,
+    /// The [`Row`]s [`App::visible_rows`] built last frame, so a redraw that
+    /// changes nothing about row content (e.g. a status-bar-only flash) can
+    /// reuse them instead of re-running [`DisplayData::as_row`] for the
+    /// whole visible range; see [`RowCache`].
+    row_cache: RowCache,
+}
+
+/// Everything [`App::visible_rows`] builds a [`Row`] from, besides the
+/// `DisplayData` already tracked on `App`. Compared against the previous
+/// frame's key to decide whether to reuse the cached rows outright, patch
+/// just the previously/newly selected row, or rebuild everything.
+#[derive(PartialEq, Clone)]
+struct RowCacheKey {
+    source_indices: Vec<usize>,
+    message_width: usize,
+    tag_width: usize,
+    zebra_striping: bool,
+    show_delta_column: bool,
+    search_highlight_hidden: bool,
+    fuzzy: bool,
+    quick_search: Option<String>,
+    pinned_highlights: Vec<String>,
+    collapsed_rows: HashSet<usize>,
+    message_scroll: usize,
+    bookmarks: BTreeSet<usize>,
+    tag_highlight_enabled: bool,
+    muted_level_color: bool,
+    selected: Option<usize>,
+    current_match_span: usize,
+}
+
+impl RowCacheKey {
+    /// Whether `self` and `other` agree on everything except `selected`/
+    /// `current_match_span` — i.e. the only thing that moved was the
+    /// selection, so only the old and new selected rows need rebuilding.
+    /// Always false while `tag_highlight_enabled`, since moving the
+    /// selection there can change which tag is highlighted, which can touch
+    /// arbitrarily many rows elsewhere in the table — not just the old and
+    /// newly selected ones.
+    fn same_except_selection(&self, other: &Self) -> bool {
+        self.source_indices == other.source_indices
+            && self.message_width == other.message_width
+            && self.tag_width == other.tag_width
+            && self.zebra_striping == other.zebra_striping
+            && self.show_delta_column == other.show_delta_column
+            && self.search_highlight_hidden == other.search_highlight_hidden
+            && self.fuzzy == other.fuzzy
+            && self.quick_search == other.quick_search
+            && self.pinned_highlights == other.pinned_highlights
+            && self.collapsed_rows == other.collapsed_rows
+            && self.message_scroll == other.message_scroll
+            && self.bookmarks == other.bookmarks
+            && self.muted_level_color == other.muted_level_color
+            && !self.tag_highlight_enabled
+            && !other.tag_highlight_enabled
+    }
+}
+
+#[derive(Default)]
+struct RowCache {
+    key: Option<RowCacheKey>,
+    rows: Vec<Row<'static>>,
+}
+
+/// Rows a single mouse-wheel tick scrolls by.
+const SCROLL_STEP: isize = 3;
+/// Characters a single `h`/`l`/`Shift-Left`/`Shift-Right` press scrolls a
+/// collapsed row's message by; see [`App::scroll_message`].
+const MESSAGE_SCROLL_STEP: usize = 4;
+/// A single `Up`/`Down`/wheel step that jumps the underlying model index by
+/// more than this is considered a "skip" worth flashing in the status bar;
+/// see [`App::move_selection`].
+const SKIPPED_ROWS_FLASH_THRESHOLD: usize = 100;
+/// Height, in terminal rows, of the table header.
+const TABLE_HEADER_HEIGHT: u16 = 1;
+/// How long a status bar flash stays visible.
+const FLASH_DURATION: Duration = Duration::from_millis(200);
+/// A filter that hides more than this fraction of the loaded entries flashes
+/// the status bar as a hint that it may be too aggressive; see
+/// [`App::refilter`].
+const AGGRESSIVE_FILTER_THRESHOLD: f64 = 0.9;
+/// How long to wait after the last keystroke in the `f` filter bar before
+/// actually re-filtering, so a large file isn't rescanned on every character.
+const FILTER_EXPR_DEBOUNCE: Duration = Duration::from_millis(150);
+/// How long the `/` quick search prompt waits after the last keystroke
+/// before re-running [`State::update`] and jumping to the nearest match,
+/// like vim's `incsearch`; short enough to feel live, long enough that
+/// fast typing against a huge file doesn't re-filter on every character.
+const QUICK_SEARCH_DEBOUNCE: Duration = Duration::from_millis(50);
+
+impl App {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        path: PathBuf,
+        requested_format: LogFormat,
+        column_width_config: ColumnWidthConfig,
+        double_click_ms: u64,
+        delta_threshold_ms: u64,
+        tz: TzOption,
+        initial_pid: Option<u32>,
+        initial_tid: Option<u32>,
+        initial_preset: Option<String>,
+        trace: bool,
+        persist_session: bool,
+    ) -> Result<Self> {
+        let format = loader::peek_format(&path, requested_format)?;
+        let background_loader = loader::spawn(path.clone(), format);
+        Self::new_with_loader(
+            path,
+            format,
+            background_loader,
+            false,
+            column_width_config,
+            double_click_ms,
+            delta_threshold_ms,
+            tz,
+            initial_pid,
+            initial_tid,
+            initial_preset,
+            trace,
+            persist_session,
+        )
+    }
+
+    /// Like [`App::new`], but for `--merge`: reads every file in `paths`
+    /// with [`load_merged_logfiles`] and interleaves them into one
+    /// timestamp-ordered view instead of opening each in its own tab (see
+    /// [`crate::tabs::Tabs`]). Unlike [`App::new`], the whole merge has to
+    /// be read before any of it can be sorted, so there's no background
+    /// loader here — the load happens synchronously, up front, and is
+    /// wrapped as an already-[`loader::LoadProgress::is_done`] loader via
+    /// [`loader::from_entries`] purely so the rest of `App` (status bar,
+    /// `Alt+D` diagnostics) doesn't need to special-case this path.
+    #[allow(clippy::too_many_arguments)]
+    pub fn new_merged(
+        paths: Vec<PathBuf>,
+        requested_format: LogFormat,
+        column_width_config: ColumnWidthConfig,
+        double_click_ms: u64,
+        delta_threshold_ms: u64,
+        tz: TzOption,
+        initial_pid: Option<u32>,
+        initial_tid: Option<u32>,
+        initial_preset: Option<String>,
+        trace: bool,
+    ) -> Result<Self> {
+        let format = loader::peek_format(&paths[0], requested_format)?;
+        let (entries, sources, encoding) = load_merged_logfiles(&paths, format)?;
+        let display_path = PathBuf::from(
+            paths
+                .iter()
+                .map(|path| path.display().to_string())
+                .collect::<Vec<_>>()
+                .join(" + "),
+        );
+        let background_loader = loader::from_entries(entries, encoding);
+        let mut app = Self::new_with_loader(
+            display_path,
+            format,
+            background_loader,
+            false,
+            column_width_config,
+            double_click_ms,
+            delta_threshold_ms,
+            tz,
+            initial_pid,
+            initial_tid,
+            initial_preset,
+            trace,
+            false,
+        )?;
+        app.merge_paths = Some(paths);
+        app.merge_sources = sources;
+        Ok(app)
+    }
+
+    /// Like [`App::new`], but streams from `journalctl --follow` instead of
+    /// a file; `unit` restricts it to a single systemd unit, as
+    /// `journalctl --unit` would. `r`/`reload` is unavailable afterwards,
+    /// since there's no file on disk to re-read — see [`App::reload`].
+    #[allow(clippy::too_many_arguments)]
+    pub fn new_journald(
+        unit: Option<String>,
+        column_width_config: ColumnWidthConfig,
+        double_click_ms: u64,
+        delta_threshold_ms: u64,
+        tz: TzOption,
+        initial_pid: Option<u32>,
+        initial_tid: Option<u32>,
+        initial_preset: Option<String>,
+        trace: bool,
+    ) -> Result<Self> {
+        let background_loader = loader::spawn_journald(unit.as_deref())?;
+        let display_path = PathBuf::from(match &unit {
+            Some(unit) => format!("journalctl --follow --unit={unit}"),
+            None => "journalctl --follow".to_string(),
+        });
+        Self::new_with_loader(
+            display_path,
+            LogFormat::Journald,
+            background_loader,
+            true,
+            column_width_config,
+            double_click_ms,
+            delta_threshold_ms,
+            tz,
+            initial_pid,
+            initial_tid,
+            initial_preset,
+            trace,
+            false,
+        )
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn new_with_loader(
+        path: PathBuf,
+        format: LogFormat,
+        background_loader: Loader,
+        journald: bool,
+        column_width_config: ColumnWidthConfig,
+        double_click_ms: u64,
+        delta_threshold_ms: u64,
+        tz: TzOption,
+        initial_pid: Option<u32>,
+        initial_tid: Option<u32>,
+        initial_preset: Option<String>,
+        trace: bool,
+        persist_session: bool,
+    ) -> Result<Self> {
+        let entries = Vec::new();
+        let column_widths = ColumnWidths::resolve(column_width_config, &entries);
+        let presets = crate::config::load_presets();
+        let active_preset = initial_preset
+            .and_then(|name| presets.iter().find(|preset| preset.name == name).cloned());
+        let restored = persist_session
+            .then(|| crate::config::load_session_state(&path))
+            .flatten();
+        let mut state = State::new();
+        state.search_history = crate::config::load_search_history();
+        state.filter.pid = initial_pid.or(restored.as_ref().and_then(|session| session.pid));
+        state.filter.tid = initial_tid.or(restored.as_ref().and_then(|session| session.tid));
+        state.filter.preset_expr = active_preset
+            .as_ref()
+            .and_then(|preset| FilterExpr::parse(&preset.expression).ok());
+        let committed_tag_filters: Vec<TagFilter> = restored
+            .as_ref()
+            .map(|session| {
+                session
+                    .tag_filters
+                    .iter()
+                    .map(|spec| TagFilter::parse(spec))
+                    .collect()
+            })
+            .unwrap_or_default();
+        state.filter.tag_filters = committed_tag_filters.clone();
+        state.filter.level_threshold = restored
+            .as_ref()
+            .and_then(|session| session.level_threshold.as_deref())
+            .and_then(|level| level.parse().ok());
+        let bookmarks: BTreeSet<usize> = restored
+            .as_ref()
+            .map(|session| session.bookmarks.clone())
+            .unwrap_or_default();
+        let pending_restore_selection = restored.and_then(|session| session.selected_entry);
+        state.update(&entries);
+        let display = DisplayData::new(&entries, display_indices(&state, false), &state.filter);
+        let status = "Loading...".to_string();
+        Ok(App {
+            path,
+            format,
+            entries,
+            table: LogTable::new(),
+            display,
+            state,
+            status,
+            should_quit: false,
+            mode: Mode::Normal,
+            input_buffer: String::new(),
+            input_cursor: 0,
+            focus: FocusedWidget::Table,
+            show_raw: false,
+            show_delta_column: false,
+            delta_highlight_threshold: chrono::Duration::milliseconds(delta_threshold_ms as i64),
+            display_tz: tz,
+            new_lines_below: 0,
+            matches_only: false,
+            search_highlight_hidden: false,
+            tag_filter_exclude: false,
+            committed_tag_filters,
+            filter_expr_text: String::new(),
+            filter_expr_error: None,
+            presets,
+            preset_picker_selected: 0,
+            active_preset: active_preset.map(|preset| preset.name),
+            tag_stats: Vec::new(),
+            tag_stats_selected: 0,
+            histogram_buckets: Vec::new(),
+            histogram_selected: 0,
+            pinned_highlights: Vec::new(),
+            pinned_selected: 0,
+            bookmarks,
+            bookmark_selected: 0,
+            detail_scroll: 0,
+            quick_filter_text: None,
+            pending_filter_expr: None,
+            pending_quick_search: None,
+            quick_search_restore: None,
+            quick_search_from_here: false,
+            search_history_cursor: None,
+            search_history_draft: None,
+            last_search_elapsed: None,
+            dirty: true,
+            table_area: Rect::default(),
+            picked: None,
+            status_flash: None,
+            column_widths,
+            column_width_config,
+            tag_width_override: None,
+            collapsed_rows: HashSet::new(),
+            message_scroll: 0,
+            last_click: None,
+            click_streak: 0,
+            double_click_ms,
+            loader: Some(background_loader),
+            started_at: Instant::now(),
+            frames: 0,
+            journald,
+            persist_session,
+            pending_restore_selection,
+            merge_paths: None,
+            merge_sources: Vec::new(),
+            debug: trace,
+            trace,
+            zebra_striping: false,
+            tag_highlight_enabled: false,
+            muted_level_color: false,
+            search_worker: None,
+            search_wrap: true,
+            current_match_span: 0,
+            row_cache: RowCache::default(),
+        })
+    }
+
+    /// Record that a frame was actually drawn, for the FPS counter.
+    pub fn record_frame(&mut self) {
+        self.frames += 1;
+    }
+
+    /// Called once per event loop iteration regardless of whether an event
+    /// arrived: drains any newly-parsed entries out of the background
+    /// [`App::loader`], drains any newly-found matches out of the background
+    /// [`App::search_worker`] (see [`App::poll_search_worker`]), applies a
+    /// debounced incremental search once it's gone quiet (see
+    /// [`App::pending_quick_search`]), then does the same for a debounced
+    /// filter expression edit. See [`App::pending_filter_expr`].
+    pub fn tick(&mut self) {
+        self.drain_loader();
+        self.poll_search_worker();
+
+        if self.new_lines_below > 0
+            && self
+                .table
+                .selected()
+                .is_some_and(|index| index + 1 == self.display.rows.len())
+        {
+            self.new_lines_below = 0;
+        }
+
+        if let Some((text, since)) = &self.pending_quick_search {
+            if since.elapsed() >= QUICK_SEARCH_DEBOUNCE {
+                let text = text.clone();
+                self.pending_quick_search = None;
+                self.run_incremental_search(&text);
+                self.dirty = true;
+            }
+        }
+
+        let Some((text, since)) = &self.pending_filter_expr else {
+            return;
+        };
+        if since.elapsed() < FILTER_EXPR_DEBOUNCE {
+            return;
+        }
+        let text = text.clone();
+        self.pending_filter_expr = None;
+        self.commit_filter_expr(&text);
+        self.dirty = true;
+    }
+
+    /// Move newly-parsed entries out of the background loader's shared
+    /// buffer and into `entries`, re-filtering and updating the status bar
+    /// with load progress (or the final entry count once done).
+    fn drain_loader(&mut self) {
+        let Some(loader) = self.loader.as_ref() else {
+            return;
+        };
+        let mut new_entries = {
+            let mut buffer = loader.entries.lock().unwrap();
+            std::mem::take(&mut *buffer)
+        };
+        let done = loader.progress.is_done();
+        let progress_status = (!done).then(|| {
+            let percent = loader
+                .progress
+                .percent()
+                .map_or_else(|| "?%".to_string(), |p| format!("{p:.0}%"));
+            format!(
+                "Loading... {percent} ({:.0} lines/s)",
+                loader.progress.lines_per_second()
+            )
+        });
+        let encoding = done.then(|| loader.progress.encoding()).flatten();
+        let lines_parsed = done.then(|| loader.progress.lines_parsed());
+        let error = done.then(|| loader.progress.error()).flatten();
+
+        if !new_entries.is_empty() {
+            let appended = new_entries.len();
+            // Only journald genuinely "streams" — a plain file's entries
+            // arrive in chunks purely as a loading-progress implementation
+            // detail (see the module doc comment), not because new lines
+            // are appearing while the user reads, so following/backlog
+            // tracking would otherwise spuriously fire on ordinary large
+            // files that take more than one chunk to parse.
+            let was_following = self.journald
+                && self
+                    .table
+                    .selected()
+                    .is_none_or(|index| index + 1 == self.display.rows.len());
+
+            self.entries.append(&mut new_entries);
+            self.refresh_column_widths();
+            self.refilter();
+
+            if let Some(entry_index) = self.pending_restore_selection {
+                if let Some(row) =
+                    display::nearest_row_for_source(&self.display.source_indices, entry_index)
+                {
+                    self.table.select(Some(row));
+                }
+                // `nearest_row_for_source` only clamps to what's loaded so
+                // far, so a restore against a row near the end of a large
+                // file would otherwise land on whatever row happened to be
+                // last when the first chunk came in and never move again
+                // (see synth-569). Keep re-resolving on every chunk and only
+                // stop once the whole file is loaded.
+                if done {
+                    self.pending_restore_selection = None;
+                }
+            }
+
+            if was_following {
+                if !self.display.rows.is_empty() {
+                    self.table.select(Some(self.display.rows.len() - 1));
+                }
+                self.new_lines_below = 0;
+            } else if self.journald {
+                self.new_lines_below += appended;
+            }
+            self.dirty = true;
+        }
+
+        if done {
+            self.status = if let Some(error) = error {
+                format!("Error: {error}")
+            } else {
+                match encoding {
+                    None | Some("UTF-8") => format!("Parsed {} entries", self.entries.len()),
+                    Some(other) => {
+                        format!("Parsed {} entries (decoded as {other})", self.entries.len())
+                    }
+                }
+            };
+            if self.trace {
+                let failed = lines_parsed
+                    .unwrap_or(0)
+                    .saturating_sub(self.entries.len() as u64);
+                if failed > 0 {
+                    self.status
+                        .push_str(&format!(" ({failed} failed to parse)"));
+                }
+            }
+            self.loader = None;
+            self.dirty = true;
+        } else if let Some(status) = progress_status {
+            self.status = status;
+            self.dirty = true;
+        }
+    }
+
+    /// Average frames-per-second since startup.
+    pub fn fps(&self) -> f64 {
+        let elapsed = self.started_at.elapsed().as_secs_f64();
+        if elapsed <= 0.0 {
+            0.0
+        } else {
+            self.frames as f64 / elapsed
+        }
+    }
+
+    pub fn selected_entry(&self) -> Option<&LogEntry> {
+        self.table.selected().and_then(|i| self.display.rows.get(i))
+    }
+
+    /// The selected row's timestamp, formatted like the Timestamp column, so
+    /// it's visible in the status bar without scrolling that column into
+    /// view. `"-"` if nothing is selected (e.g. an empty file).
+    pub fn selected_timestamp(&self) -> String {
+        self.selected_entry()
+            .map(|entry| {
+                (entry.timestamp + self.display_tz.offset())
+                    .format("%m-%d %H:%M:%S%.f")
+                    .to_string()
+            })
+            .unwrap_or_else(|| "-".to_string())
+    }
+
+    /// How many rows have streamed in since the selection stopped tracking
+    /// the latest line; see [`App::new_lines_below`]. Zero while following.
+    pub fn new_lines_below(&self) -> usize {
+        self.new_lines_below
+    }
+
+    /// Indices into `entries` of the rows currently passing every active
+    /// filter, in display order. Precomputed in [`App::refilter`]/
+    /// [`App::reload`] whenever a filter changes, so `draw`, row-count
+    /// lookups, and search never rescan the full model on every frame — see
+    /// the timing note on [`crate::filter::apply`].
+    pub fn visible_indices(&self) -> &[usize] {
+        &self.display.source_indices
+    }
+
+    /// Time elapsed between the selected row and the closest earlier row
+    /// sharing the same tag, or `None` if there isn't one.
+    pub fn time_since_last_same_tag(&self) -> Option<chrono::Duration> {
+        let selected_index = self.table.selected()?;
+        let selected = self.display.rows.get(selected_index)?;
+        self.display.rows[..selected_index]
+            .iter()
+            .rev()
+            .find(|entry| entry.tag == selected.tag)
+            .map(|previous| selected.timestamp - previous.timestamp)
+    }
+
+    /// Re-read the original file from disk, rebuild the table and display
+    /// data, and try to keep the cursor on the same absolute line.
+    pub fn reload(&mut self) -> Result<()> {
+        if self.journald {
+            self.status = "Reload is not available when streaming from journald".to_string();
+            return Ok(());
+        }
+        let (entries, sources, encoding) = match &self.merge_paths {
+            Some(paths) => load_merged_logfiles(paths, self.format)?,
+            None => {
+                let (entries, _, encoding) = load_logfile(&self.path, self.format)?;
+                (entries, Vec::new(), encoding)
+            }
+        };
+        let previous_line = self.table.selected();
+
+        // Any in-flight scan was matching against the old `entries`; its
+        // indices would be meaningless (or wrong) against the reloaded file.
+        self.cancel_search_worker();
+        self.entries = entries;
+        self.merge_sources = sources;
+        self.column_widths = ColumnWidths::resolve(self.column_width_config, &self.entries);
+        self.state.update(&self.entries);
+        self.display = DisplayData::new(
+            &self.entries,
+            display_indices(&self.state, self.matches_only),
+            &self.state.filter,
+        );
+
+        let last_index = self.display.rows.len().saturating_sub(1);
+        let restored = previous_line.map(|line| line.min(last_index));
+        self.table.select(restored);
+
+        self.status = match encoding {
+            "UTF-8" => format!("Reloaded {} entries", self.display.rows.len()),
+            other => format!(
+                "Reloaded {} entries (decoded as {other})",
+                self.display.rows.len()
+            ),
+        };
+        Ok(())
+    }
+
+    /// Write the scroll position, filters, and bookmarks out to
+    /// `~/.local/share/logcatui/session_state.json`, keyed by `path`, for
+    /// [`App::new`] to restore on a later run. No-op unless
+    /// `--persist-session` was given, and for `--merge`/`--journald`
+    /// sessions, which have no single on-disk file to key by. Called once
+    /// from `main` on quit.
+    pub fn save_session(&self) {
+        if !self.persist_session || self.journald || self.merge_paths.is_some() {
+            return;
+        }
+        let selected_entry = self
+            .table
+            .selected()
+            .and_then(|row| self.display.source_indices.get(row))
+            .copied();
+        let tag_filters = self
+            .committed_tag_filters
+            .iter()
+            .map(|filter| {
+                format!(
+                    "{}{}{}",
+                    if filter.exclude { "!" } else { "" },
+                    if filter.fuzzy { "~" } else { "" },
+                    filter.pattern
+                )
+            })
+            .collect();
+        crate::config::save_session_state(
+            &self.path,
+            crate::config::PersistedSession {
+                selected_entry,
+                level_threshold: self
+                    .state
+                    .filter
+                    .level_threshold
+                    .map(|level| level.to_string()),
+                tag_filters,
+                pid: self.state.filter.pid,
+                tid: self.state.filter.tid,
+                bookmarks: self.bookmarks.clone(),
+                ..Default::default()
+            },
+        );
+    }
+
+    /// Recompute `state.filtered_indices`/`display` after a filter change,
+    /// keeping the selection on the same entry if it's still visible.
+    fn refilter(&mut self) {
+        // A running scan was matching against the old `filtered_indices`;
+        // once the filter changes, its candidate set (and thus its matches)
+        // is stale.
+        self.cancel_search_worker();
+        let selected_source = self
+            .table
+            .selected()
+            .and_then(|i| self.display.source_indices.get(i))
+            .copied();
+
+        self.state.update(&self.entries);
+        self.display = DisplayData::new(
+            &self.entries,
+            display_indices(&self.state, self.matches_only),
+            &self.state.filter,
+        );
+
+        let restored = selected_source
+            .and_then(|source| {
+                display::nearest_row_for_source(&self.display.source_indices, source)
+            })
+            .or(if self.display.rows.is_empty() {
+                None
+            } else {
+                Some(0)
+            });
+        self.table.select(restored);
+
+        if self.state.filter.is_active() && !self.entries.is_empty() {
+            let hidden_fraction = self.hidden_row_count() as f64 / self.entries.len() as f64;
+            if hidden_fraction > AGGRESSIVE_FILTER_THRESHOLD {
+                self.flash(styles::filter_indicator_color());
+            }
+        }
+    }
+
+    /// How many loaded entries the active filter is currently hiding, i.e.
+    /// not in [`App::matching_row_count`].
+    pub fn hidden_row_count(&self) -> usize {
+        self.entries.len() - self.matching_row_count()
+    }
+
+    /// Clear every filter and the active search in one action: the
+    /// min-level, tag, PID/TID, preset, free-form `f`, and `Ctrl+X` quick
+    /// filters, plus whatever `/` search is currently highlighting. Bound to
+    /// `\` so a view built up over a long investigation can be abandoned
+    /// without undoing each criterion one at a time.
+    fn reset_view(&mut self) {
+        self.state.filter = Filter::default();
+        self.committed_tag_filters.clear();
+        self.filter_expr_text.clear();
+        self.filter_expr_error = None;
+        self.active_preset = None;
+        self.quick_filter_text = None;
+        self.state.quick_search = None;
+        self.quick_search_restore = None;
+        self.refilter();
+        self.status = "View reset: filters and search cleared".to_string();
+    }
+
+    /// Status bar label for `--tz`, e.g. `"Local"` or `"UTC+05:30"`; `None`
+    /// for the default [`TzOption::Utc`], so sessions that never asked for a
+    /// timezone conversion don't get an extra status bar segment.
+    pub fn display_tz_label(&self) -> Option<String> {
+        self.display_tz.label()
+    }
+
+    /// A compact summary of every active filter criterion plus the hidden
+    /// row count, for the status bar indicator (e.g. `level≥W  tag:Camera
+    /// -38211 rows`); `None` if no filter is active, so the segment and its
+    /// separator disappear entirely rather than showing an empty one.
+    pub fn filter_indicator(&self) -> Option<String> {
+        if !self.state.filter.is_active() {
+            return None;
+        }
+        let filter = &self.state.filter;
+        let mut parts = Vec::new();
+        if let Some(level) = filter.level_threshold {
+            parts.push(format!("level\u{2265}{level}"));
+        }
+        if !filter.tag_filters.is_empty() {
+            parts.push(format!("tag:{}", tag_filter_summary(&filter.tag_filters)));
+        }
+        if let Some(pid) = filter.pid {
+            parts.push(format!("pid={pid}"));
+        }
+        if let Some(tid) = filter.tid {
+            parts.push(format!("tid={tid}"));
+        }
+        if filter.expr.is_some() {
+            parts.push(format!("expr:{}", self.filter_expr_text));
+        }
+        if let Some(name) = &self.active_preset {
+            parts.push(format!("preset:{name}"));
+        }
+        if let Some(text) = &self.quick_filter_text {
+            parts.push(format!("quick:{text}"));
+        }
+        parts.push(format!("-{} rows", self.hidden_row_count()));
+        Some(parts.join("  "))
+    }
+
+    /// Toggle the FPS/timing segment of the status bar, hidden by default.
+    fn toggle_debug(&mut self) {
+        self.debug = !self.debug;
+        self.status = if self.debug {
+            "Debug info on".to_string()
+        } else {
+            "Debug info off".to_string()
+        };
+    }
+
+    /// Toggle alternating row backgrounds, off by default.
+    fn toggle_zebra_striping(&mut self) {
+        self.zebra_striping = !self.zebra_striping;
+        self.status = if self.zebra_striping {
+            "Zebra striping on".to_string()
+        } else {
+            "Zebra striping off".to_string()
+        };
+    }
+
+    /// `Alt+G`: toggle highlighting the Tag cell of every row sharing the
+    /// selected row's tag, off by default; see
+    /// [`App::tag_highlight_enabled`].
+    fn toggle_tag_highlight(&mut self) {
+        self.tag_highlight_enabled = !self.tag_highlight_enabled;
+        self.status = if self.tag_highlight_enabled {
+            "Tag highlight on".to_string()
+        } else {
+            "Tag highlight off".to_string()
+        };
+    }
+
+    /// `Alt+C`: toggle whether the row's log-level tint is dimmed; see
+    /// [`App::muted_level_color`].
+    fn toggle_muted_level_color(&mut self) {
+        self.muted_level_color = !self.muted_level_color;
+        self.status = if self.muted_level_color {
+            "Muted level color on".to_string()
+        } else {
+            "Muted level color off".to_string()
+        };
+    }
+
+    /// Toggle the inter-line time delta column, off by default; see
+    /// [`App::show_delta_column`].
+    fn toggle_delta_column(&mut self) {
+        self.show_delta_column = !self.show_delta_column;
+        self.status = if self.show_delta_column {
+            "Delta column on".to_string()
+        } else {
+            "Delta column off".to_string()
+        };
+    }
+
+    /// `<`/`>`/`Alt+Left`/`Alt+Right`: grow or shrink the Tag column by one,
+    /// trading space with the Message column, which always takes whatever's
+    /// left. The new width sticks for the rest of the session (see
+    /// [`App::tag_width_override`]) until overridden again.
+    fn adjust_tag_width(&mut self, delta: i16) {
+        self.column_widths.adjust_tag_width(delta);
+        self.tag_width_override = Some(self.column_widths.tag);
+        self.status = format!("Tag column width: {}", self.column_widths.tag);
+    }
+
+    /// Recomputes `column_widths` from `column_width_config`/`entries`, then
+    /// reapplies `tag_width_override` on top if one is set. Used instead of
+    /// a raw `ColumnWidths::resolve` call everywhere `column_widths` needs
+    /// recomputing (after streaming in new entries, after a reload), so an
+    /// interactive `<`/`>` override isn't silently lost.
+    fn refresh_column_widths(&mut self) {
+        self.column_widths = ColumnWidths::resolve(self.column_width_config, &self.entries);
+        if let Some(tag_width) = self.tag_width_override {
+            self.column_widths.tag = tag_width;
+        }
+    }
+
+    /// Toggle whether `n`/`N` wrap around at the first/last match, on by
+    /// default.
+    fn toggle_search_wrap(&mut self) {
+        self.search_wrap = !self.search_wrap;
+        self.status = if self.search_wrap {
+            "Search wrap on".to_string()
+        } else {
+            "Search wrap off".to_string()
+        };
+    }
+
+    /// Cycle the minimum-level filter: off → V → D → I → W → E → F → off.
+    fn cycle_level_threshold(&mut self) {
+        let current = LEVEL_CYCLE
+            .iter()
+            .position(|&threshold| threshold == self.state.filter.level_threshold)
+            .unwrap_or(0);
+        let next = LEVEL_CYCLE[(current + 1) % LEVEL_CYCLE.len()];
+        self.set_level_threshold(next);
+    }
+
+    fn set_level_threshold(&mut self, threshold: Option<LogLevel>) {
+        let total = self.entries.len();
+        self.state.filter.level_threshold = threshold;
+        self.refilter();
+        self.status = match threshold {
+            Some(level) => format!(
+                "Level >= {level} ({} of {total} hidden)",
+                total - self.display.rows.len()
+            ),
+            None => "Level filter off".to_string(),
+        };
+    }
+
+    /// Cycle the sort column: off → Time → PID → TID → Level → Tag → off.
+    /// Resets the direction to ascending each time a new column is picked;
+    /// see [`App::toggle_sort_direction`] to flip it.
+    fn cycle_sort_column(&mut self) {
+        let current = SORT_CYCLE
+            .iter()
+            .position(|&column| column == self.state.sort_column)
+            .unwrap_or(0);
+        let next = SORT_CYCLE[(current + 1) % SORT_CYCLE.len()];
+        self.state.sort_column = next;
+        self.state.sort_direction = SortDirection::Ascending;
+        self.refilter();
+        self.status = match next {
+            Some(column) => format!("Sorted by {column} ({})", self.state.sort_direction),
+            None => "Sort off".to_string(),
+        };
+    }
+
+    /// Flip ascending/descending for the active sort column. No-op if no
+    /// column is sorted — there's nothing to reverse.
+    fn toggle_sort_direction(&mut self) {
+        let Some(column) = self.state.sort_column else {
+            self.status = "No active sort to reverse".to_string();
+            return;
+        };
+        self.state.sort_direction = match self.state.sort_direction {
+            SortDirection::Ascending => SortDirection::Descending,
+            SortDirection::Descending => SortDirection::Ascending,
+        };
+        self.refilter();
+        self.status = format!("Sorted by {column} ({})", self.state.sort_direction);
+    }
+
+    /// Toggle restricting the view to the PID of the currently selected row:
+    /// pressing `p` again on a row with the same PID clears the filter. Does
+    /// nothing if no row is selected.
+    fn toggle_selected_pid(&mut self) {
+        let Some(pid) = self.selected_entry().map(|entry| entry.pid) else {
+            return;
+        };
+        let pid = if self.state.filter.pid == Some(pid) {
+            None
+        } else {
+            Some(pid)
+        };
+        self.set_pid_filter(pid);
+    }
+
+    /// Toggle restricting the view to the TID of the currently selected row;
+    /// see [`App::toggle_selected_pid`].
+    fn toggle_selected_tid(&mut self) {
+        let Some(tid) = self.selected_entry().map(|entry| entry.tid) else {
+            return;
+        };
+        let tid = if self.state.filter.tid == Some(tid) {
+            None
+        } else {
+            Some(tid)
+        };
+        self.set_tid_filter(tid);
+    }
+
+    fn set_pid_filter(&mut self, pid: Option<u32>) {
+        self.state.filter.pid = pid;
+        self.refilter();
+        self.status = match pid {
+            Some(pid) => format!("pid={pid}"),
+            None => "pid filter off".to_string(),
+        };
+    }
+
+    fn set_tid_filter(&mut self, tid: Option<u32>) {
+        self.state.filter.tid = tid;
+        self.refilter();
+        self.status = match tid {
+            Some(tid) => format!("tid={tid}"),
+            None => "tid filter off".to_string(),
+        };
+    }
+
+    /// Open the `Ctrl+X` quick filter prompt, or, if a quick filter is
+    /// already active, remove it. No-op (with a status message) if nothing
+    /// is selected to build a filter from.
+    fn toggle_quick_filter_prompt(&mut self) {
+        if self.state.filter.quick_filter.is_some() {
+            self.state.filter.quick_filter = None;
+            self.quick_filter_text = None;
+            self.refilter();
+            self.status = "Quick filter removed".to_string();
+            return;
+        }
+        if self.selected_entry().is_none() {
+            self.status = "No row selected".to_string();
+            return;
+        }
+        self.mode = Mode::QuickFilter;
+    }
+
+    /// Handle the single key following `Ctrl+X`: `p`/`t`/`l`/`m` build an
+    /// include filter from the selected row's PID/tag/level/first message
+    /// word, their Shift'd forms build an exclude filter, and any other key
+    /// cancels without creating one.
+    pub fn quick_filter_input(&mut self, key: KeyEvent) {
+        self.dirty = true;
+        self.mode = Mode::Normal;
+        let Some(entry) = self.selected_entry() else {
+            return;
+        };
+        let field = match key.code {
+            KeyCode::Char('p') | KeyCode::Char('P') => QuickFilterField::Pid,
+            KeyCode::Char('t') | KeyCode::Char('T') => QuickFilterField::Tag,
+            KeyCode::Char('l') | KeyCode::Char('L') => QuickFilterField::Level,
+            KeyCode::Char('m') | KeyCode::Char('M') => QuickFilterField::Message,
+            _ => return,
+        };
+        let exclude = matches!(
+            key.code,
+            KeyCode::Char('P') | KeyCode::Char('T') | KeyCode::Char('L') | KeyCode::Char('M')
+        );
+        let text = quick_filter_expr_text(field, exclude, entry);
+        let Ok(expr) = FilterExpr::parse(&text) else {
+            return;
+        };
+        self.state.filter.quick_filter = Some(expr);
+        self.quick_filter_text = Some(text.clone());
+        self.refilter();
+        self.status = format!("Quick filter: {text}");
+    }
+
+    /// Copy the selected row's level and tag in the compact `"L/TAG"` form
+    /// used by `adb logcat` filter specs, e.g. `"E/ActivityManager"`. Distinct
+    /// from copying the full line or just the message.
+    fn copy_level_tag(&mut self) {
+        let Some(entry) = self.selected_entry() else {
+            return;
+        };
+        let spec = format!("{}/{}", entry.level, entry.tag);
+        self.status = match ClipboardContext::set_text(&spec) {
+            Ok(()) => format!("Copied filter spec: {spec}"),
+            Err(err) => format!("Failed to copy to clipboard: {err}"),
+        };
+    }
+
+    /// Toggle between hiding non-matching rows and keeping them on screen,
+    /// dimmed, for context. No-op if no filter is currently active — with
+    /// nothing set, every row matches, so there'd be nothing to dim.
+    fn toggle_display_mode(&mut self) {
+        if !self.state.filter.is_active() {
+            self.status = "No active filter to dim".to_string();
+            return;
+        }
+        self.state.filter.display_mode = match self.state.filter.display_mode {
+            DisplayMode::Hide => DisplayMode::Dim,
+            DisplayMode::Dim => DisplayMode::Hide,
+        };
+        self.refilter();
+        self.status = match self.state.filter.display_mode {
+            DisplayMode::Dim => format!("Context mode: {} matching", self.matching_row_count()),
+            DisplayMode::Hide => "Context mode off".to_string(),
+        };
+    }
+
+    /// Count of rows in `display` that match the active filter, i.e. aren't
+    /// dimmed. Equal to `display.rows.len()` outside [`DisplayMode::Dim`].
+    pub fn matching_row_count(&self) -> usize {
+        self.display
+            .dimmed
+            .iter()
+            .filter(|&&dimmed| !dimmed)
+            .count()
+    }
+
+    pub fn display_mode(&self) -> DisplayMode {
+        self.state.filter.display_mode
+    }
+
+    /// Display-row indices of the active quick-search matches, for
+    /// [`crate::ui::draw_scrollbar`]'s tick marks. Empty when no search is
+    /// active, same as [`App::jump_to_match`] treats it.
+    pub fn match_row_indices(&self) -> Vec<usize> {
+        if self.state.quick_search.is_none() {
+            return Vec::new();
+        }
+        let matches = self.search_result_set();
+        self.display
+            .source_indices
+            .iter()
+            .enumerate()
+            .filter(|(_, source)| matches.contains(source))
+            .map(|(row, _)| row)
+            .collect()
+    }
+
+    /// Display-row indices of the set bookmarks, for
+    /// [`crate::ui::draw_scrollbar`]'s tick marks. A bookmark hidden by the
+    /// active filter has no display row and is simply absent here, same as
+    /// [`App::jump_to_bookmark`] skips it.
+    pub fn bookmark_row_indices(&self) -> Vec<usize> {
+        self.display
+            .source_indices
+            .iter()
+            .enumerate()
+            .filter(|(_, source)| self.bookmarks.contains(source))
+            .map(|(row, _)| row)
+            .collect()
+    }
+
+    /// Move the selection to the next (`delta > 0`) or previous (`delta < 0`)
+    /// interesting row: a quick-search match (`state.results`) while a
+    /// search is active, otherwise a row that matches the active filter,
+    /// skipping dimmed context rows. While a search is active, steps through
+    /// the individual match positions on the selected line (see
+    /// [`App::current_match_span`]) before moving to a different row. No-op
+    /// if nothing is selected or no such row exists. While a search is
+    /// active and [`App::search_wrap`] is on, running off the end wraps to
+    /// the first/last match instead of stopping, like vim's `wrapscan`, and
+    /// the status bar flashes and notes the wrap. Updates the status bar
+    /// with the new match position; see [`App::current_match_rank`].
+    fn jump_to_match(&mut self, delta: isize) {
+        let Some(current) = self.table.selected() else {
+            return;
+        };
+        let len = self.display.rows.len();
+        if len == 0 {
+            return;
+        }
+        self.search_highlight_hidden = false;
+        let step = delta.signum();
+        let searching = self.state.quick_search.is_some();
+        let search_matches = searching.then(|| self.search_result_set());
+        let is_match = |index: usize| match &search_matches {
+            Some(matches) => self
+                .display
+                .source_indices
+                .get(index)
+                .is_some_and(|source| matches.contains(source)),
+            None => !self.display.dimmed[index],
+        };
+        let wrap = searching && self.search_wrap;
+
+        if searching && is_match(current) {
+            if let Some(pattern) = self.state.quick_search.clone() {
+                if let Some(entry) = self.display.rows.get(current) {
+                    let spans = self.active_match_spans(&entry.message, &pattern);
+                    let next_span = self.current_match_span as isize + step;
+                    if !spans.is_empty() && next_span >= 0 && (next_span as usize) < spans.len() {
+                        self.current_match_span = next_span as usize;
+                        self.status = self.quick_search_status();
+                        return;
+                    }
+                }
+            }
+        }
+
+        let mut index = current as isize;
+        let mut wrapped = false;
+        loop {
+            index += step;
+            if index < 0 || index >= len as isize {
+                if !wrap || wrapped {
+                    return;
+                }
+                wrapped = true;
+                index = if step > 0 { 0 } else { len as isize - 1 };
+            }
+            if wrapped && index as usize == current {
+                // Circled back to the start without finding another match.
+                return;
+            }
+            if is_match(index as usize) {
+                self.table.select(Some(index as usize));
+                self.current_match_span = self.last_match_span_index(index as usize, step);
+                if searching {
+                    self.status = if wrapped {
+                        self.flash(Color::Magenta);
+                        format!("{} — search wrapped", self.quick_search_status())
+                    } else {
+                        self.quick_search_status()
+                    };
+                }
+                return;
+            }
+        }
+    }
+
+    /// The span index [`App::jump_to_match`] should land on when entering
+    /// row `index` from a different row: the first match position when
+    /// moving forward, the last when moving backward, so `N` into a line
+    /// lands on the same position `n` would leave from.
+    fn last_match_span_index(&self, index: usize, step: isize) -> usize {
+        if step > 0 {
+            return 0;
+        }
+        let Some(pattern) = &self.state.quick_search else {
+            return 0;
+        };
+        let Some(entry) = self.display.rows.get(index) else {
+            return 0;
+        };
+        self.active_match_spans(&entry.message, pattern)
+            .len()
+            .saturating_sub(1)
+    }
+
+    /// Byte range of the current quick-search match span on the selected
+    /// row, for [`crate::display::DisplayData::as_row`] to style distinctly
+    /// from the rest; `None` outside a search, if nothing is selected, or
+    /// the selected row has no matches in its message (e.g. the pattern
+    /// only matched the tag). See [`App::current_match_span`].
+    pub fn current_match_span(&self) -> Option<(usize, usize)> {
+        let pattern = self.state.quick_search.as_ref()?;
+        let selected = self.table.selected()?;
+        let entry = self.display.rows.get(selected)?;
+        let spans = self.active_match_spans(&entry.message, pattern);
+        spans
+            .get(self.current_match_span)
+            .or(spans.first())
+            .copied()
+    }
+
+    /// `search::match_spans`, or its fuzzy equivalent when `state.fuzzy` is
+    /// on; see [`App::jump_to_match`], [`App::last_match_span_index`], and
+    /// [`App::current_match_span`].
+    fn active_match_spans(&self, message: &str, pattern: &str) -> Vec<(usize, usize)> {
+        if self.state.fuzzy {
+            search::fuzzy_match_spans(message, pattern)
+        } else {
+            search::match_spans(message, pattern)
+        }
+    }
+
+    /// `state.results` as a set, for membership checks against
+    /// `display.source_indices`; see [`App::jump_to_match`] and
+    /// [`App::jump_to_nearest_match`].
+    fn search_result_set(&self) -> HashSet<usize> {
+        self.state.results.iter().copied().collect()
+    }
+
+    /// Move the selection to the next (`delta > 0`) or previous (`delta <
+    /// 0`) visible row whose level is at or above `threshold`, independent
+    /// of any active quick search. Does not wrap; reports in the status bar
+    /// when there is no such row in that direction. Bound to `e`/`E` for
+    /// errors and `w`/`W` for warnings in [`App::regular_input`].
+    fn jump_to_level(&mut self, delta: isize, threshold: LogLevel) {
+        let Some(current) = self.table.selected() else {
+            return;
+        };
+        let len = self.display.rows.len();
+        if len == 0 {
+            return;
+        }
+        let step = delta.signum();
+        let label = if threshold >= LogLevel::Error {
+            "error"
+        } else {
+            "warning"
+        };
+
+        let mut index = current as isize;
+        loop {
+            index += step;
+            if index < 0 || index >= len as isize {
+                self.status = format!(
+                    "No further {label}s {}",
+                    if step > 0 { "below" } else { "above" }
+                );
+                return;
+            }
+            if self.display.rows[index as usize].level >= threshold {
+                self.table.select(Some(index as usize));
+                self.status = format!("At {label} (row {})", index + 1);
+                return;
+            }
+        }
+    }
+
+    /// `]`/`[`: move the selection to the next (`delta > 0`) or previous
+    /// (`delta < 0`) visible row sharing the selected row's tag. Does not
+    /// wrap; reports in the status bar when there is no such row in that
+    /// direction, and how many further rows with the same tag remain beyond
+    /// the one just selected, e.g. "tag ActivityManager: 23 more below".
+    fn jump_to_same_tag(&mut self, delta: isize) {
+        let Some(current) = self.table.selected() else {
+            return;
+        };
+        let Some(entry) = self.display.rows.get(current) else {
+            return;
+        };
+        let tag = entry.tag.clone();
+        self.jump_to_matching(delta, &format!("tag {tag}"), |entry| entry.tag == tag);
+    }
+
+    /// `Alt+]`/`Alt+[`: like [`App::jump_to_same_tag`], but for rows sharing
+    /// the selected row's PID.
+    fn jump_to_same_pid(&mut self, delta: isize) {
+        let Some(current) = self.table.selected() else {
+            return;
+        };
+        let Some(entry) = self.display.rows.get(current) else {
+            return;
+        };
+        let pid = entry.pid;
+        self.jump_to_matching(delta, &format!("pid {pid}"), |entry| entry.pid == pid);
+    }
+
+    /// Shared walk behind [`App::jump_to_same_tag`]/[`App::jump_to_same_pid`]:
+    /// move to the next (`delta > 0`) or previous (`delta < 0`) visible row
+    /// for which `matches` holds, then keep scanning in the same direction
+    /// to report how many further matches remain, so a long run of the same
+    /// tag/PID doesn't need to be stepped through one row at a time to see
+    /// how much more there is.
+    fn jump_to_matching(&mut self, delta: isize, label: &str, matches: impl Fn(&LogEntry) -> bool) {
+        let Some(current) = self.table.selected() else {
+            return;
+        };
+        let len = self.display.rows.len();
+        if len == 0 {
+            return;
+        }
+        let step = delta.signum();
+        let direction = if step > 0 { "below" } else { "above" };
+
+        let mut index = current as isize;
+        loop {
+            index += step;
+            if index < 0 || index >= len as isize {
+                self.status = format!("No further {label} {direction}");
+                return;
+            }
+            if matches(&self.display.rows[index as usize]) {
+                break;
+            }
+        }
+
+        let mut further = 0;
+        let mut scan = index;
+        loop {
+            scan += step;
+            if scan < 0 || scan >= len as isize {
+                break;
+            }
+            if matches(&self.display.rows[scan as usize]) {
+                further += 1;
+            }
+        }
+
+        self.table.select(Some(index as usize));
+        self.status = if further > 0 {
+            format!("{label}: {further} more {direction}")
+        } else {
+            format!("{label}: last match {direction}")
+        };
+    }
+
+    /// `Ctrl-B`: toggle a bookmark on the selected row, keyed by its index
+    /// into `entries` rather than the display row so it survives filtering;
+    /// see [`App::bookmarks`] and [`App::jump_to_bookmark`].
+    fn toggle_bookmark(&mut self) {
+        let Some(selected) = self.table.selected() else {
+            return;
+        };
+        let Some(&source_index) = self.display.source_indices.get(selected) else {
+            return;
+        };
+        self.status = if self.bookmarks.remove(&source_index) {
+            "Bookmark removed".to_string()
+        } else {
+            self.bookmarks.insert(source_index);
+            "Bookmark set".to_string()
+        };
+    }
+
+    /// `'`/`` ` ``: move the selection to the next (`delta > 0`) or previous
+    /// (`delta < 0`) visible bookmarked row. Does not wrap; reports in the
+    /// status bar when there is no such row in that direction. A bookmark on
+    /// a row hidden by the active filter is skipped here, not removed — see
+    /// [`App::toggle_bookmark`].
+    fn jump_to_bookmark(&mut self, delta: isize) {
+        let Some(current) = self.table.selected() else {
+            return;
+        };
+        let len = self.display.rows.len();
+        if len == 0 {
+            return;
+        }
+        if self.bookmarks.is_empty() {
+            self.status = "No bookmarks set".to_string();
+            return;
+        }
+        let step = delta.signum();
+        let mut index = current as isize;
+        loop {
+            index += step;
+            if index < 0 || index >= len as isize {
+                self.status = format!(
+                    "No further bookmarks {}",
+                    if step > 0 { "below" } else { "above" }
+                );
+                return;
+            }
+            let is_bookmarked = self
+                .display
+                .source_indices
+                .get(index as usize)
+                .is_some_and(|source_index| self.bookmarks.contains(source_index));
+            if is_bookmarked {
+                self.table.select(Some(index as usize));
+                self.status = format!("At bookmark (row {})", index + 1);
+                return;
+            }
+        }
+    }
+
+    pub fn move_selection(&mut self, delta: isize) {
+        let len = self.display.rows.len();
+        if len == 0 {
+            return;
+        }
+        let current = self.table.selected().unwrap_or(0) as isize;
+        let next = (current + delta).clamp(0, len as isize - 1);
+        if let (Some(&from), Some(&to)) = (
+            self.display.source_indices.get(current as usize),
+            self.display.source_indices.get(next as usize),
+        ) {
+            let skipped = from.abs_diff(to);
+            if skipped > SKIPPED_ROWS_FLASH_THRESHOLD {
+                self.status = format!("Skipped {skipped} rows (filtered)");
+            }
+        }
+        self.table.select(Some(next as usize));
+        self.current_match_span = 0;
+        if let Some(entry) = self.selected_entry() {
+            if entry.level == LogLevel::Error {
+                self.flash(Color::Red);
+            }
+        }
+    }
+
+    /// Rows per `PageUp`/`PageDown`: the table body's current height, so a
+    /// page always moves by exactly one screenful.
+    fn page_step(&self) -> isize {
+        (self.table_area.height.saturating_sub(TABLE_HEADER_HEIGHT) as isize).max(1)
+    }
+
+    /// Rows per `Ctrl-D`/`Ctrl-U`: half of [`App::page_step`], like vim's
+    /// half-page scroll, so context from before the jump stays on screen
+    /// instead of a full `PageDown`/`PageUp` replacing it entirely.
+    fn half_page_step(&self) -> isize {
+        (self.page_step() / 2).max(1)
+    }
+
+    /// Cycle keyboard focus among whichever of [`FocusedWidget`]'s variants
+    /// are actually open right now. Today that's only ever `Table`, since
+    /// this codebase has no persistent search bar or sidebar to focus — see
+    /// the doc comment on [`FocusedWidget`].
+    fn cycle_focus(&mut self) {
+        self.focus = FocusedWidget::Table;
+        self.status = "Focus: Table (search bar / sidebar not implemented yet)".to_string();
+    }
+
+    /// Briefly color the status bar background with `color`, for tactile
+    /// feedback on an important navigation event.
+    fn flash(&mut self, color: Color) {
+        self.status_flash = Some((color, Instant::now()));
+    }
+
+    /// Toggle whether the selected row renders as a single truncated line
+    /// (collapsed) or wraps its full message across multiple lines.
+    fn toggle_selected_row_wrap(&mut self) {
+        let Some(display_index) = self.table.selected() else {
+            return;
+        };
+        let Some(&source_index) = self.display.source_indices.get(display_index) else {
+            return;
+        };
+        if !self.collapsed_rows.remove(&source_index) {
+            self.collapsed_rows.insert(source_index);
+        }
+    }
+
+    /// Whether row `display_index` is currently collapsed; see
+    /// [`App::toggle_selected_row_wrap`].
+    pub fn is_row_collapsed(&self, display_index: usize) -> bool {
+        self.display
+            .source_indices
+            .get(display_index)
+            .is_some_and(|source_index| self.collapsed_rows.contains(source_index))
+    }
+
+    /// Scroll the (table-wide) collapsed-row message window by `delta`
+    /// characters, clamped so it never scrolls past the selected row's
+    /// message. Only collapsed rows are affected; see
+    /// [`App::message_scroll`] and [`display::DisplayData::as_row`].
+    fn scroll_message(&mut self, delta: isize) {
+        let max_scroll = self
+            .selected_entry()
+            .map_or(0, |entry| entry.message.chars().count());
+        self.message_scroll = self
+            .message_scroll
+            .saturating_add_signed(delta)
+            .min(max_scroll);
+    }
+
+    /// The color the status bar should flash this frame, or `None` if no
+    /// flash is active or it has expired.
+    pub fn active_flash_color(&self) -> Option<Color> {
+        self.status_flash
+            .filter(|(_, started)| started.elapsed() < FLASH_DURATION)
+            .map(|(color, _)| color)
+    }
+
+    /// Handle a key event while in the normal (non-search, non-filter) mode.
+    pub fn regular_input(&mut self, key: KeyEvent) -> Result<()> {
+        self.dirty = true;
+        match (key.code, key.modifiers) {
+            (KeyCode::Char('q'), _) => self.should_quit = true,
+            (KeyCode::Char('r'), KeyModifiers::NONE)
+            | (KeyCode::Char('r'), KeyModifiers::CONTROL) => {
+                self.reload()?;
+            }
+            (KeyCode::Char('p'), KeyModifiers::CONTROL) => {
+                self.picked = self.selected_entry().map(|entry| entry.to_string());
+                self.should_quit = true;
+            }
+            (KeyCode::Char('p') | KeyCode::Char('P'), KeyModifiers::ALT) => {
+                self.open_pinned_highlights();
+            }
+            (KeyCode::Char('p'), _) => self.toggle_selected_pid(),
+            (KeyCode::Char('P'), _) => self.toggle_selected_tid(),
+            (KeyCode::Enter, _) => self.toggle_selected_row_wrap(),
+            (KeyCode::Down, _) | (KeyCode::Char('j'), _) => self.move_selection(1),
+            (KeyCode::Up, _) | (KeyCode::Char('k'), _) => self.move_selection(-1),
+            (KeyCode::PageDown, _) => self.move_selection(self.page_step()),
+            (KeyCode::PageUp, _) => self.move_selection(-self.page_step()),
+            (KeyCode::Char('d'), KeyModifiers::CONTROL) => {
+                self.move_selection(self.half_page_step())
+            }
+            (KeyCode::Char('u'), KeyModifiers::CONTROL) => {
+                self.move_selection(-self.half_page_step());
+            }
+            (KeyCode::Char('g') | KeyCode::Char('G'), KeyModifiers::ALT) => {
+                self.toggle_tag_highlight();
+            }
+            (KeyCode::Char('c') | KeyCode::Char('C'), KeyModifiers::ALT) => {
+                self.toggle_muted_level_color();
+            }
+            (KeyCode::Char('g'), _) | (KeyCode::Home, _) => self.jump_to_first_row(),
+            (KeyCode::Char('G'), _) | (KeyCode::End, _) => self.jump_to_last_row(),
+            (KeyCode::Char('e'), KeyModifiers::CONTROL) => self.scroll_viewport(1),
+            (KeyCode::Char('y'), KeyModifiers::CONTROL) => self.scroll_viewport(-1),
+            (KeyCode::Char(':'), _) => {
+                self.mode = Mode::Command;
+                self.set_input_buffer(String::new());
+            }
+            (KeyCode::Char('x'), KeyModifiers::CONTROL) => self.toggle_quick_filter_prompt(),
+            (KeyCode::Char('\\'), _) => self.reset_view(),
+            (KeyCode::Char('h'), KeyModifiers::CONTROL) => self.pin_current_search(),
+            (KeyCode::Char('l'), KeyModifiers::CONTROL) => self.clear_pinned_highlights(),
+            (KeyCode::Char('t'), KeyModifiers::CONTROL) => {
+                self.committed_tag_filters.clear();
+                self.state.filter.tag_filters.clear();
+                self.refilter();
+                self.status = format!("Tag filters: {}", tag_filter_summary(&[]));
+            }
+            (KeyCode::Char('t') | KeyCode::Char('T'), KeyModifiers::ALT) => {
+                self.toggle_delta_column();
+            }
+            (KeyCode::Char('<'), _) | (KeyCode::Left, KeyModifiers::ALT) => {
+                self.adjust_tag_width(-1);
+            }
+            (KeyCode::Char('>'), _) | (KeyCode::Right, KeyModifiers::ALT) => {
+                self.adjust_tag_width(1);
+            }
+            (KeyCode::Char('t'), _) => {
+                self.mode = Mode::TagFilter;
+                self.tag_filter_exclude = false;
+                self.set_input_buffer(String::new());
+            }
+            (KeyCode::Char('T'), _) => {
+                self.mode = Mode::TagFilter;
+                self.tag_filter_exclude = true;
+                self.set_input_buffer(String::new());
+            }
+            (KeyCode::Char('l') | KeyCode::Char('L'), KeyModifiers::ALT) => self.copy_level_tag(),
+            (KeyCode::Char('r') | KeyCode::Char('R'), KeyModifiers::ALT) => {
+                self.show_raw = !self.show_raw;
+                self.status = if self.show_raw {
+                    "Raw view on".to_string()
+                } else {
+                    "Raw view off".to_string()
+                };
+            }
+            (KeyCode::Char('d') | KeyCode::Char('D'), KeyModifiers::ALT) => self.toggle_debug(),
+            (KeyCode::Char('z') | KeyCode::Char('Z'), KeyModifiers::ALT) => {
+                self.toggle_zebra_striping();
+            }
+            (KeyCode::Char('z') | KeyCode::Char('Z'), _) => self.center_selected_row(),
+            (KeyCode::Char('w') | KeyCode::Char('W'), KeyModifiers::ALT) => {
+                self.toggle_search_wrap();
+            }
+            (KeyCode::Char('s') | KeyCode::Char('S'), KeyModifiers::ALT) => {
+                self.open_tag_stats();
+            }
+            (KeyCode::Char('h') | KeyCode::Char('H'), KeyModifiers::ALT) => {
+                self.open_histogram();
+            }
+            (KeyCode::Left, KeyModifiers::SHIFT) | (KeyCode::Char('h'), _) => {
+                self.scroll_message(-(MESSAGE_SCROLL_STEP as isize));
+            }
+            (KeyCode::Right, KeyModifiers::SHIFT) | (KeyCode::Char('l'), _) => {
+                self.scroll_message(MESSAGE_SCROLL_STEP as isize);
+            }
+            (KeyCode::Char('v'), _) => self.toggle_display_mode(),
+            (KeyCode::Char('/'), KeyModifiers::ALT) => {
+                self.mode = Mode::QuickSearch;
+                self.set_input_buffer(self.state.quick_search.clone().unwrap_or_default());
+                self.quick_search_restore =
+                    Some((self.state.quick_search.clone(), self.table.selected()));
+                self.quick_search_from_here = true;
+                self.search_history_cursor = None;
+                self.search_history_draft = None;
+            }
+            (KeyCode::Char('/'), _) => {
+                self.mode = Mode::QuickSearch;
+                self.set_input_buffer(self.state.quick_search.clone().unwrap_or_default());
+                self.quick_search_restore =
+                    Some((self.state.quick_search.clone(), self.table.selected()));
+                self.quick_search_from_here = false;
+                self.search_history_cursor = None;
+                self.search_history_draft = None;
+            }
+            (KeyCode::Char('m'), _) => self.toggle_matches_only(),
+            (KeyCode::Char('b'), KeyModifiers::CONTROL) => self.toggle_bookmark(),
+            (KeyCode::Char('\''), _) => self.jump_to_bookmark(1),
+            (KeyCode::Char('`'), _) => self.jump_to_bookmark(-1),
+            (KeyCode::Char('M'), _) => self.open_bookmarks(),
+            (KeyCode::Char(' ') | KeyCode::Char('o'), _) => self.open_entry_detail(),
+            (KeyCode::Char('n'), KeyModifiers::CONTROL) => self.toggle_search_highlight(),
+            (KeyCode::Char('n'), _) => self.jump_to_match(1),
+            (KeyCode::Char('N'), _) => self.jump_to_match(-1),
+            (KeyCode::Char('e'), _) => self.jump_to_level(1, LogLevel::Error),
+            (KeyCode::Char('E'), _) => self.jump_to_level(-1, LogLevel::Error),
+            (KeyCode::Char('w'), _) => self.jump_to_level(1, LogLevel::Warn),
+            (KeyCode::Char('W'), _) => self.jump_to_level(-1, LogLevel::Warn),
+            (KeyCode::Char(']'), KeyModifiers::ALT) => self.jump_to_same_pid(1),
+            (KeyCode::Char('['), KeyModifiers::ALT) => self.jump_to_same_pid(-1),
+            (KeyCode::Char(']'), _) => self.jump_to_same_tag(1),
+            (KeyCode::Char('['), _) => self.jump_to_same_tag(-1),
+            (KeyCode::Char('*'), KeyModifiers::ALT) => self.search_under_cursor(true),
+            (KeyCode::Char('*'), _) => self.search_under_cursor(false),
+            (KeyCode::Tab, _) | (KeyCode::Char('i'), KeyModifiers::CONTROL) => self.cycle_focus(),
+            (KeyCode::Esc, _) => self.focus = FocusedWidget::Table,
+            (KeyCode::Char('f'), _) => {
+                self.mode = Mode::FilterExpr;
+                self.set_input_buffer(self.filter_expr_text.clone());
+                self.filter_expr_error = None;
+            }
+            (KeyCode::Char('F'), _) => self.open_preset_picker(),
+            (KeyCode::Char('L'), _) => self.cycle_level_threshold(),
+            (KeyCode::Char('s'), _) => self.cycle_sort_column(),
+            (KeyCode::Char('S'), _) => self.toggle_sort_direction(),
+            (KeyCode::Char('0'), _) => self.set_level_threshold(None),
+            (KeyCode::Char(digit @ '1'..='6'), _) => {
+                let level = match digit {
+                    '1' => LogLevel::Verbose,
+                    '2' => LogLevel::Debug,
+                    '3' => LogLevel::Info,
+                    '4' => LogLevel::Warn,
+                    '5' => LogLevel::Error,
+                    _ => LogLevel::Fatal,
+                };
+                self.set_level_threshold(Some(level));
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Handle `Event::Resize`. The layout and column widths are already
+    /// recomputed from the terminal size on every [`crate::ui::draw`] call,
+    /// and ratatui's own `Terminal::draw` resizes its buffers to match
+    /// before rendering, so there's no stale geometry here to re-clamp;
+    /// this just forces the next iteration of the event loop to redraw
+    /// immediately instead of waiting for `dirty` to be set some other way
+    /// (e.g. the next keypress), which on some terminals can otherwise
+    /// leave a stale frame on screen right after a resize.
+    pub fn resize(&mut self) {
+        self.dirty = true;
+    }
+
+    /// Handle a mouse event: wheel scrolling moves the selection, and
+    /// clicking a row selects it.
+    pub fn handle_mouse(&mut self, event: MouseEvent) {
+        self.dirty = true;
+        match event.kind {
+            MouseEventKind::ScrollDown => self.move_selection(SCROLL_STEP),
+            MouseEventKind::ScrollUp => self.move_selection(-SCROLL_STEP),
+            MouseEventKind::Down(_) => {
+                if let Some(index) = self.row_at(event.column, event.row) {
+                    self.table.select(Some(index));
+                    self.current_match_span = 0;
+                    self.register_click(index, event.column);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Translate a click at terminal position `(column, row)` into an index
+    /// into `display.rows`, or `None` if it falls outside the table body.
+    fn row_at(&self, column: u16, row: u16) -> Option<usize> {
+        if !self
+            .table_area
+            .contains(ratatui::layout::Position { x: column, y: row })
+        {
+            return None;
+        }
+        let body_top = self.table_area.y + TABLE_HEADER_HEIGHT;
+        if row < body_top {
+            return None;
+        }
+        let offset = self.table.state.offset();
+        let index = offset + usize::from(row - body_top);
+        (index < self.display.rows.len()).then_some(index)
+    }
+
+    /// Track consecutive clicks on the same row within `double_click_ms`:
+    /// a double-click toggles row wrapping (same as `Enter`), a triple-click
+    /// opens the command palette pre-filled for the clicked column's filter.
+    fn register_click(&mut self, row: usize, column: u16) {
+        let now = Instant::now();
+        self.click_streak = match self.last_click {
+            Some((last_row, last_time))
+                if last_row == row
+                    && now.duration_since(last_time)
+                        < Duration::from_millis(self.double_click_ms) =>
+            {
+                self.click_streak + 1
+            }
+            _ => 1,
+        };
+        self.last_click = Some((row, now));
+
+        match self.click_streak {
+            2 => self.toggle_selected_row_wrap(),
+            3 => self.open_command_palette_for_column(column),
+            _ => {}
+        }
+    }
+
+    /// Open `:` command mode pre-filled with the filter command matching
+    /// whichever column `column` (an absolute terminal x-coordinate) falls
+    /// in, or empty if it's over the message column.
+    fn open_command_palette_for_column(&mut self, column: u16) {
+        let prefix = match self.column_at(column) {
+            TableColumn::Pid => "filter-pid ",
+            TableColumn::Tid => "filter-tid ",
+            TableColumn::Tag => "filter-tag ",
+            TableColumn::Timestamp
+            | TableColumn::Delta
+            | TableColumn::Level
+            | TableColumn::Message => "",
+        };
+        self.mode = Mode::Command;
+        self.set_input_buffer(prefix);
+    }
+
+    /// Which table column an absolute terminal x-coordinate falls in.
+    fn column_at(&self, x: u16) -> TableColumn {
+        let relative = x.saturating_sub(self.table_area.x);
+        let widths = self.column_widths;
+        let mut columns = vec![(widths.timestamp, TableColumn::Timestamp)];
+        if self.show_delta_column {
+            columns.push((crate::ui::DELTA_COLUMN_WIDTH, TableColumn::Delta));
+        }
+        columns.extend([
+            (widths.pid, TableColumn::Pid),
+            (TID_COLUMN_WIDTH, TableColumn::Tid),
+            (LEVEL_COLUMN_WIDTH, TableColumn::Level),
+            (widths.tag, TableColumn::Tag),
+        ]);
+
+        let mut offset = 0;
+        for (width, column) in columns {
+            offset += width;
+            if relative < offset {
+                return column;
+            }
+        }
+        TableColumn::Message
+    }
+
+    /// Replace `input_buffer` wholesale (e.g. opening a prompt pre-filled,
+    /// or recalling search history) and put the cursor at the end of it,
+    /// which is what every caller wants.
+    fn set_input_buffer(&mut self, text: impl Into<String>) {
+        self.input_buffer = text.into();
+        self.input_cursor = self.input_buffer.len();
+    }
+
+    /// Insert `c` at the cursor and advance past it. Shared by every
+    /// `input_buffer`-editing mode (`Command`, `TagFilter`, `FilterExpr`,
+    /// `QuickSearch`).
+    fn input_insert(&mut self, c: char) {
+        self.input_buffer.insert(self.input_cursor, c);
+        self.input_cursor += c.len_utf8();
+    }
+
+    /// `Backspace`: delete the character before the cursor.
+    fn input_backspace(&mut self) {
+        if let Some(previous) = self.input_buffer[..self.input_cursor].chars().next_back() {
+            let start = self.input_cursor - previous.len_utf8();
+            self.input_buffer.drain(start..self.input_cursor);
+            self.input_cursor = start;
+        }
+    }
+
+    /// `Delete`: delete the character at the cursor.
+    fn input_delete(&mut self) {
+        if let Some(next) = self.input_buffer[self.input_cursor..].chars().next() {
+            self.input_buffer
+                .drain(self.input_cursor..self.input_cursor + next.len_utf8());
+        }
+    }
+
+    /// `Left`: move the cursor back one character.
+    fn input_move_left(&mut self) {
+        if let Some(previous) = self.input_buffer[..self.input_cursor].chars().next_back() {
+            self.input_cursor -= previous.len_utf8();
+        }
+    }
+
+    /// `Right`: move the cursor forward one character.
+    fn input_move_right(&mut self) {
+        if let Some(next) = self.input_buffer[self.input_cursor..].chars().next() {
+            self.input_cursor += next.len_utf8();
+        }
+    }
+
+    /// `Ctrl-W`: delete the word before the cursor, readline-style — skip
+    /// any trailing whitespace, then delete back through the run of
+    /// same-class characters before it (Unicode alphanumeric-or-underscore
+    /// counts as one class, everything else non-whitespace as another), so
+    /// e.g. `foo.bar|` deletes to `foo.|`, then to `foo|`, and then to `|`.
+    fn input_delete_word_before(&mut self) {
+        let is_word_char = |c: char| c.is_alphanumeric() || c == '_';
+        let mut chars = self.input_buffer[..self.input_cursor]
+            .char_indices()
+            .rev()
+            .peekable();
+        let mut start = self.input_cursor;
+        while let Some(&(index, ch)) = chars.peek() {
+            if !ch.is_whitespace() {
+                break;
+            }
+            start = index;
+            chars.next();
+        }
+        if let Some(&(_, ch)) = chars.peek() {
+            let word = is_word_char(ch);
+            while let Some(&(index, ch)) = chars.peek() {
+                if ch.is_whitespace() || is_word_char(ch) != word {
+                    break;
+                }
+                start = index;
+                chars.next();
+            }
+        }
+        self.input_buffer.drain(start..self.input_cursor);
+        self.input_cursor = start;
+    }
+
+    /// `Ctrl-U`: delete from the start of the buffer up to the cursor.
+    fn input_clear_to_start(&mut self) {
+        self.input_buffer.drain(..self.input_cursor);
+        self.input_cursor = 0;
+    }
+
+    /// Insert `text` at the cursor, stripping newlines so a multi-line
+    /// clipboard paste (or a bracketed-paste event) collapses onto one line
+    /// instead of corrupting the single-line input buffer. Used for both
+    /// `Ctrl-V` (see [`App::handle_input_editing_key`]) and terminal
+    /// bracketed-paste events (see [`App::handle_paste`]).
+    fn input_paste(&mut self, text: &str) {
+        let normalized: String = text.chars().filter(|c| *c != '\n' && *c != '\r').collect();
+        self.input_buffer.insert_str(self.input_cursor, &normalized);
+        self.input_cursor += normalized.len();
+    }
+
+    /// Handle a bracketed-paste event from the terminal (see
+    /// [`crossterm::event::Event::Paste`]): delivered as one event with the
+    /// full pasted text, rather than the flood of individual `KeyCode::Char`
+    /// key events a terminal without bracketed-paste support would send. A
+    /// no-op outside the text-input modes.
+    pub fn handle_paste(&mut self, text: &str) {
+        if !matches!(
+            self.mode,
+            Mode::Command | Mode::TagFilter | Mode::FilterExpr | Mode::QuickSearch
+        ) {
+            return;
+        }
+        self.dirty = true;
+        self.input_paste(text);
+        if self.mode == Mode::QuickSearch {
+            self.pending_quick_search = Some((self.input_buffer.clone(), Instant::now()));
+        }
+    }
+
+    /// Handle `Left`/`Right`/`Home`/`End`/`Ctrl-W`/`Ctrl-U`/`Delete` common to
+    /// every `input_buffer`-editing mode. Returns `true` if `key` was one of
+    /// these and has been handled; callers fall through to their own
+    /// mode-specific bindings (`Enter`, `Esc`, history, ...) on `false`.
+    fn handle_input_editing_key(&mut self, key: KeyEvent) -> bool {
+        match (key.code, key.modifiers) {
+            (KeyCode::Left, _) => self.input_move_left(),
+            (KeyCode::Right, _) => self.input_move_right(),
+            (KeyCode::Home, _) => self.input_cursor = 0,
+            (KeyCode::End, _) => self.input_cursor = self.input_buffer.len(),
+            (KeyCode::Delete, _) => self.input_delete(),
+            (KeyCode::Char('w'), KeyModifiers::CONTROL) => self.input_delete_word_before(),
+            (KeyCode::Char('u'), KeyModifiers::CONTROL) => self.input_clear_to_start(),
+            (KeyCode::Char('v'), KeyModifiers::CONTROL) => match ClipboardContext::get_text() {
+                Ok(text) => self.input_paste(&text),
+                Err(err) => self.status = format!("Failed to paste from clipboard: {err}"),
+            },
+            _ => return false,
+        }
+        true
+    }
+
+    /// Handle a key event while typing a `:` command.
+    pub fn command_input(&mut self, key: KeyEvent) {
+        self.dirty = true;
+        if self.handle_input_editing_key(key) {
+            return;
+        }
+        match key.code {
+            KeyCode::Esc => {
+                self.mode = Mode::Normal;
+                self.input_buffer.clear();
+                self.input_cursor = 0;
+            }
+            KeyCode::Enter => {
+                self.apply_command();
+                self.mode = Mode::Normal;
+            }
+            KeyCode::Backspace => self.input_backspace(),
+            KeyCode::Char(c) => self.input_insert(c),
+            _ => {}
+        }
+    }
+
+    /// Parse and apply the command currently in `input_buffer`, e.g.
+    /// `filter-tag ~ActivityManag` or `goto 48213`; see [`App::goto_line`].
+    /// `filter-tag` replaces any tag filters stacked via the `t`/`T` prompt
+    /// rather than adding to them.
+    fn apply_command(&mut self) {
+        let command = self.input_buffer.trim();
+        if let Some(pattern) = command.strip_prefix("filter-tag ") {
+            self.committed_tag_filters = vec![TagFilter::parse(pattern.trim())];
+            self.state.filter.tag_filters = self.committed_tag_filters.clone();
+            self.refilter();
+            self.status = format!(
+                "Tag filters: {}",
+                tag_filter_summary(&self.committed_tag_filters)
+            );
+        } else if let Some(pid) = command.strip_prefix("filter-pid ") {
+            if let Ok(pid) = pid.trim().parse() {
+                self.set_pid_filter(Some(pid));
+            }
+        } else if let Some(tid) = command.strip_prefix("filter-tid ") {
+            if let Ok(tid) = tid.trim().parse() {
+                self.set_tid_filter(Some(tid));
+            }
+        } else if let Some(arg) = command.strip_prefix("goto ") {
+            let arg = arg.trim().to_string();
+            self.goto_line(&arg);
+        }
+        self.input_buffer.clear();
+        self.input_cursor = 0;
+    }
+
+    /// `:goto N` jumps to the `N`th currently visible row (1-based, like a
+    /// line number); `:goto +N`/`:goto -N` jump `N` rows forward/backward
+    /// from the current selection instead. Either way the target clamps to
+    /// the currently displayed rows rather than wrapping or erroring at the
+    /// ends. Non-numeric input sets a status message rather than being
+    /// silently ignored. Flashes the row the same way
+    /// [`App::move_selection`] does for a big skip, since a `:goto` is
+    /// always a deliberate jump away from the current position.
+    fn goto_line(&mut self, arg: &str) {
+        let len = self.display.rows.len();
+        if len == 0 {
+            self.status = "No rows to jump to".to_string();
+            return;
+        }
+
+        let current = self.table.selected().unwrap_or(0) as isize;
+        let target = if let Some(delta) = arg.strip_prefix('+') {
+            match delta.parse::<isize>() {
+                Ok(delta) => current + delta,
+                Err(_) => {
+                    self.status = format!("Not a line number: {arg}");
+                    return;
+                }
+            }
+        } else if arg.starts_with('-') {
+            match arg.parse::<isize>() {
+                Ok(delta) => current + delta,
+                Err(_) => {
+                    self.status = format!("Not a line number: {arg}");
+                    return;
+                }
+            }
+        } else {
+            match arg.parse::<isize>() {
+                Ok(line) => line - 1,
+                Err(_) => {
+                    self.status = format!("Not a line number: {arg}");
+                    return;
+                }
+            }
+        };
+        let target = target.clamp(0, len as isize - 1) as usize;
+
+        self.table.select(Some(target));
+        self.current_match_span = 0;
+        self.flash(Color::Cyan);
+        self.status = format!("Line {} of {len}", target + 1);
+    }
+
+    /// `g`/`Home`: jump to the first currently visible row.
+    fn jump_to_first_row(&mut self) {
+        self.jump_to_row(0);
+    }
+
+    /// `G`/`End`: jump to the last currently visible row. Ratatui's own
+    /// `Table` widget recomputes `TableState`'s scroll offset from the
+    /// selected row and every row's wrapped height on the next render, so
+    /// just selecting the last row is enough to land it at the bottom of
+    /// the viewport rather than the top with blank space below — no manual
+    /// offset math needed here.
+    fn jump_to_last_row(&mut self) {
+        self.jump_to_row(self.display.rows.len().saturating_sub(1));
+    }
+
+    /// Shared by [`App::jump_to_first_row`]/[`App::jump_to_last_row`].
+    /// Flashes the row the same way [`App::goto_line`] does, since these
+    /// are always a deliberate jump away from the current position.
+    fn jump_to_row(&mut self, target: usize) {
+        if self.display.rows.is_empty() {
+            return;
+        }
+        self.table.select(Some(target));
+        self.current_match_span = 0;
+        self.flash(Color::Cyan);
+        self.status = format!("Line {} of {}", target + 1, self.display.rows.len());
+    }
+
+    /// `Ctrl-E`/`Ctrl-Y`: shift the viewport by one row without moving the
+    /// selection, like vim. `delta` is rows to shift the top of the viewport
+    /// down (positive) or up (negative). The selected row itself doesn't
+    /// move; ratatui's own `Table` widget snaps the offset back as soon as
+    /// it would scroll the selection out of view (see
+    /// [`App::jump_to_last_row`]'s doc comment), so there's nothing further
+    /// to clamp here beyond not underflowing the offset itself.
+    fn scroll_viewport(&mut self, delta: isize) {
+        let offset = self.table.state.offset();
+        let next = (offset as isize + delta).max(0) as usize;
+        *self.table.state.offset_mut() = next;
+    }
+
+    /// `z`/`Z`: center the selected row vertically in the viewport.
+    fn center_selected_row(&mut self) {
+        let Some(selected) = self.table.selected() else {
+            return;
+        };
+        let half_page = self.page_step() / 2;
+        let offset = selected.saturating_sub(half_page as usize);
+        *self.table.state.offset_mut() = offset;
+    }
+
+    /// Handle a key event while typing a tag filter opened with `t` (include)
+    /// or `T` (exclude). Every keystroke re-applies the stack of already
+    /// committed filters plus the in-progress one, so the table updates live.
+    /// `Esc` discards the in-progress filter only; `Enter` folds it into
+    /// `committed_tag_filters` so a subsequent `t`/`T` stacks on top of it.
+    pub fn tag_filter_input(&mut self, key: KeyEvent) {
+        self.dirty = true;
+        if self.handle_input_editing_key(key) {
+            self.apply_tag_filter_input();
+            return;
+        }
+        match key.code {
+            KeyCode::Esc => {
+                self.input_buffer.clear();
+                self.input_cursor = 0;
+                self.apply_tag_filter_input();
+                self.mode = Mode::Normal;
+                return;
+            }
+            KeyCode::Enter => {
+                if !self.input_buffer.trim().is_empty() {
+                    let mut filter = TagFilter::parse(self.input_buffer.trim());
+                    filter.exclude = self.tag_filter_exclude;
+                    self.committed_tag_filters.push(filter);
+                }
+                self.input_buffer.clear();
+                self.input_cursor = 0;
+                self.apply_tag_filter_input();
+                self.mode = Mode::Normal;
+                return;
+            }
+            KeyCode::Backspace => self.input_backspace(),
+            KeyCode::Char(c) => self.input_insert(c),
+            _ => return,
+        }
+        self.apply_tag_filter_input();
+    }
+
+    /// Recompute `state.filter.tag_filters` as `committed_tag_filters` plus
+    /// the live, not-yet-committed pattern in `input_buffer` (if any), then
+    /// refilter and update the status bar.
+    fn apply_tag_filter_input(&mut self) {
+        let mut filters = self.committed_tag_filters.clone();
+        if !self.input_buffer.is_empty() {
+            let mut live = TagFilter::parse(&self.input_buffer);
+            live.exclude = self.tag_filter_exclude;
+            filters.push(live);
+        }
+        self.state.filter.tag_filters = filters;
+        self.refilter();
+        self.status = format!(
+            "Tag filters: {}",
+            tag_filter_summary(&self.state.filter.tag_filters)
+        );
+    }
+
+    /// Handle a key event while typing a quick search opened with `/`, like
+    /// vim's `incsearch`: every keystroke schedules [`App::run_incremental_search`]
+    /// after `QUICK_SEARCH_DEBOUNCE` (see [`App::tick`]) instead of
+    /// re-filtering immediately, so fast typing against a huge file doesn't
+    /// re-run the search on every character. `Esc` discards the edit,
+    /// putting the pattern and selection back exactly as they were before
+    /// the prompt opened (see [`App::quick_search_restore`]); `Enter`
+    /// commits the jumped-to match and returns to normal mode — to the
+    /// nearest match in either direction if the prompt was opened with `/`,
+    /// or the first match at or after the current row if opened with
+    /// `Alt+/` (see [`App::quick_search_from_here`]). `Ctrl-F` toggles
+    /// fzf-style fuzzy matching (see [`State::fuzzy`]) and re-schedules the
+    /// debounced search so the toggle takes effect immediately.
+    ///
+    /// [`State::fuzzy`]: crate::state::State::fuzzy
+    pub fn quick_search_input(&mut self, key: KeyEvent) {
+        self.dirty = true;
+        if key.code == KeyCode::Char('f') && key.modifiers.contains(KeyModifiers::CONTROL) {
+            self.state.fuzzy = !self.state.fuzzy;
+            self.pending_quick_search = Some((self.input_buffer.clone(), Instant::now()));
+            return;
+        }
+        if self.handle_input_editing_key(key) {
+            self.pending_quick_search = Some((self.input_buffer.clone(), Instant::now()));
+            return;
+        }
+        match key.code {
+            KeyCode::Esc => {
+                self.pending_quick_search = None;
+                self.cancel_search_worker();
+                self.search_history_cursor = None;
+                self.search_history_draft = None;
+                self.quick_search_from_here = false;
+                let (pattern, selected) = self.quick_search_restore.take().unwrap_or_default();
+                self.state.quick_search = pattern;
+                self.refilter();
+                self.current_match_span = 0;
+                self.table
+                    .select(selected.filter(|&index| index < self.display.rows.len()));
+                self.input_buffer.clear();
+                self.input_cursor = 0;
+                self.status = self.quick_search_status();
+                self.mode = Mode::Normal;
+                return;
+            }
+            KeyCode::Enter => {
+                self.pending_quick_search = None;
+                self.quick_search_restore = None;
+                self.search_history_cursor = None;
+                self.search_history_draft = None;
+                let mut pattern = std::mem::take(&mut self.input_buffer);
+                self.input_cursor = 0;
+                if pattern.is_empty() {
+                    if let Some(last) = self.state.search_history.first() {
+                        pattern = last.clone();
+                    }
+                }
+                if !pattern.is_empty() {
+                    self.state.record_search(pattern.clone());
+                    crate::config::save_search_history(&self.state.search_history);
+                }
+                self.run_incremental_search(&pattern);
+                self.mode = Mode::Normal;
+                return;
+            }
+            KeyCode::Up => {
+                if self.state.search_history.is_empty() {
+                    return;
+                }
+                let next = match self.search_history_cursor {
+                    None => {
+                        self.search_history_draft = Some(self.input_buffer.clone());
+                        0
+                    }
+                    Some(index) => (index + 1).min(self.state.search_history.len() - 1),
+                };
+                self.search_history_cursor = Some(next);
+                self.set_input_buffer(self.state.search_history[next].clone());
+            }
+            KeyCode::Down => match self.search_history_cursor {
+                None => return,
+                Some(0) => {
+                    self.search_history_cursor = None;
+                    let restored = self.search_history_draft.take().unwrap_or_default();
+                    self.set_input_buffer(restored);
+                }
+                Some(index) => {
+                    let next = index - 1;
+                    self.search_history_cursor = Some(next);
+                    self.set_input_buffer(self.state.search_history[next].clone());
+                }
+            },
+            KeyCode::Backspace => self.input_backspace(),
+            KeyCode::Char(c) => self.input_insert(c),
+            _ => return,
+        }
+        self.pending_quick_search = Some((self.input_buffer.clone(), Instant::now()));
+    }
+
+    /// Re-run the quick search for `pattern` (`state.quick_search` is
+    /// `None` if blank): re-filter inline (cheap), then hand the
+    /// search-matching step itself off to a [`search_worker`], since that's
+    /// the part that gets slow on a huge file, rather than blocking here
+    /// until every row has been scanned. Any scan already in flight for the
+    /// previous pattern is cancelled first. This is the debounced
+    /// incremental-search apply; see [`App::tick`] and
+    /// [`App::quick_search_input`].
+    fn run_incremental_search(&mut self, pattern: &str) {
+        self.cancel_search_worker();
+        self.state.quick_search = (!pattern.is_empty()).then(|| pattern.to_string());
+        self.current_match_span = 0;
+        self.search_highlight_hidden = false;
+        let started = Instant::now();
+        self.state.refresh_filter(&self.entries);
+        self.state.results.clear();
+        self.last_search_elapsed = Some(started.elapsed());
+
+        if let Some(pattern) = self.state.quick_search.clone() {
+            let candidates: Vec<(usize, LogEntry)> = self
+                .state
+                .filtered_indices
+                .iter()
+                .map(|&index| (index, self.entries[index].clone()))
+                .collect();
+            self.search_worker = Some(search_worker::spawn(candidates, pattern, self.state.fuzzy));
+        }
+
+        self.display = DisplayData::new(
+            &self.entries,
+            display_indices(&self.state, self.matches_only),
+            &self.state.filter,
+        );
+        if self.quick_search_from_here {
+            self.jump_to_first_match_from_here();
+        } else {
+            self.jump_to_nearest_match();
+        }
+        self.status = self.quick_search_status();
+    }
+
+    /// `*`/`Alt+*`, like vim's `*`/`g*`: quick-search for a token taken
+    /// from the selected row — its tag for `*`, the first word of its
+    /// message for `Alt+*` — record it in search history the same way
+    /// `Enter` in [`Mode::QuickSearch`] does, and jump past the row it came
+    /// from to the next occurrence, so repeated presses actually advance.
+    /// The token is left in `state.quick_search`, so `n`/`N` keep working
+    /// on it afterwards. No-op if nothing is selected or the chosen token
+    /// is empty.
+    fn search_under_cursor(&mut self, use_message: bool) {
+        let Some(entry) = self.selected_entry() else {
+            return;
+        };
+        let token = if use_message {
+            entry.message.split_whitespace().next()
+        } else {
+            Some(entry.tag.as_str())
+        };
+        let Some(token) = token.filter(|t| !t.is_empty()) else {
+            return;
+        };
+        let token = token.to_string();
+
+        self.state.record_search(token.clone());
+        crate::config::save_search_history(&self.state.search_history);
+        self.run_incremental_search(&token);
+        self.jump_to_match(1);
+    }
+
+    /// Drain newly-found matches out of [`App::search_worker`] into
+    /// `state.results`, jumping to the nearest one if nothing was navigable
+    /// yet, and refreshing the status bar (showing "Searching…" progress
+    /// while the scan is still running). No-op if no scan is in flight.
+    fn poll_search_worker(&mut self) {
+        let Some(worker) = self.search_worker.as_ref() else {
+            return;
+        };
+        let had_no_results = self.state.results.is_empty();
+        let new_matches = {
+            let mut buffer = worker.matches.lock().unwrap();
+            std::mem::take(&mut *buffer)
+        };
+        let done = worker.is_done();
+
+        if !new_matches.is_empty() {
+            self.state.results.extend(new_matches);
+            self.display = DisplayData::new(
+                &self.entries,
+                display_indices(&self.state, self.matches_only),
+                &self.state.filter,
+            );
+            if had_no_results {
+                if self.quick_search_from_here {
+                    self.jump_to_first_match_from_here();
+                } else {
+                    self.jump_to_nearest_match();
+                }
+            }
+            self.dirty = true;
+        }
+
+        if done {
+            self.search_worker = None;
+            self.dirty = true;
+        }
+        self.status = self.quick_search_status();
+    }
+
+    /// Tell any in-flight [`App::search_worker`] to stop, so a stale scan
+    /// for a pattern the user has already moved past doesn't keep running;
+    /// see [`App::run_incremental_search`] and [`App::quick_search_input`]'s
+    /// `Esc` handling.
+    fn cancel_search_worker(&mut self) {
+        if let Some(worker) = self.search_worker.take() {
+            worker.cancel();
+        }
+    }
+
+    /// Move the selection to the closest row (by display-row distance) whose
+    /// source entry is in `state.results`, leaving it alone if it's already
+    /// a match. No-op with no active search, an empty table, or no match at
+    /// all.
+    fn jump_to_nearest_match(&mut self) {
+        if self.state.quick_search.is_none() || self.display.rows.is_empty() {
+            return;
+        }
+        let matches = self.search_result_set();
+        let is_match = |index: usize| {
+            self.display
+                .source_indices
+                .get(index)
+                .is_some_and(|source| matches.contains(source))
+        };
+        let current = self.table.selected().unwrap_or(0);
+        if is_match(current) {
+            return;
+        }
+        let nearest = (0..self.display.rows.len())
+            .filter(|&index| is_match(index))
+            .min_by_key(|&index| index.abs_diff(current));
+        if let Some(index) = nearest {
+            self.table.select(Some(index));
+            self.current_match_span = 0;
+        }
+    }
+
+    /// Move the selection to the first match at or after the current row,
+    /// like `less`'s `/`, without considering matches before it. Entered via
+    /// `Alt+/` (see [`App::quick_search_from_here`]) as an alternative to
+    /// [`App::jump_to_nearest_match`]'s either-direction search; `n`/`N`
+    /// behave identically afterwards either way, since `jump_to_match`
+    /// always continues from the current selection. No-op with no active
+    /// search, an empty table, or no match at or after the current row.
+    fn jump_to_first_match_from_here(&mut self) {
+        if self.state.quick_search.is_none() || self.display.rows.is_empty() {
+            return;
+        }
+        let matches = self.search_result_set();
+        let is_match = |index: usize| {
+            self.display
+                .source_indices
+                .get(index)
+                .is_some_and(|source| matches.contains(source))
+        };
+        let current = self.table.selected().unwrap_or(0);
+        if let Some(index) = (current..self.display.rows.len()).find(|&index| is_match(index)) {
+            self.table.select(Some(index));
+            self.current_match_span = 0;
+        }
+    }
+
+    /// 1-based rank of the currently selected row among `state.results`,
+    /// and the total match count, or `None` if nothing is selected or the
+    /// selection isn't itself a match. See [`App::jump_to_match`].
+    fn current_match_rank(&self) -> Option<(usize, usize)> {
+        let selected = self.table.selected()?;
+        let source = *self.display.source_indices.get(selected)?;
+        let rank = self
+            .state
+            .results
+            .iter()
+            .position(|&index| index == source)?
+            + 1;
+        Some((rank, self.state.results.len()))
+    }
+
+    /// The `Search: ...` status line for the active `state.quick_search`:
+    /// `match R/N` when the selection is on a match (e.g. after `n`/`N`),
+    /// otherwise just the total match count so far, plus, once one has run,
+    /// how long the most recent incremental re-filter took. While
+    /// [`App::search_worker`] is still scanning, shows a "searching…" note
+    /// instead, since the count is only partial until it finishes.
+    fn quick_search_status(&self) -> String {
+        match &self.state.quick_search {
+            Some(pattern) => {
+                let elapsed = self
+                    .last_search_elapsed
+                    .map(|elapsed| format!(", {:.1}ms", elapsed.as_secs_f64() * 1000.0))
+                    .unwrap_or_default();
+                let count = match self.current_match_rank() {
+                    Some((rank, total)) => format!("match {rank}/{total}"),
+                    None if self.search_worker.is_some() => {
+                        format!("searching… {} so far", self.state.results.len())
+                    }
+                    None => format!("{} matches", self.state.results.len()),
+                };
+                let mode = if self.state.fuzzy { " fuzzy" } else { "" };
+                let highlight = if self.search_highlight_hidden {
+                    ", highlight off"
+                } else {
+                    ""
+                };
+                format!("Search:{mode} {pattern} ({count}{elapsed}{highlight})")
+            }
+            None => "Search: none".to_string(),
+        }
+    }
+
+    /// `Ctrl-N`, vim's `:noh`: suppress search-match highlighting in the
+    /// table without touching `state.quick_search`/`state.results`, so `n`/`N`
+    /// still work right afterwards — it just stops painting every match
+    /// yellow until the next navigation. No-op if no quick search is active.
+    /// See [`App::search_highlight_hidden`].
+    fn toggle_search_highlight(&mut self) {
+        if self.state.quick_search.is_none() {
+            self.status = "No active search to clear highlighting for".to_string();
+            return;
+        }
+        self.search_highlight_hidden = !self.search_highlight_hidden;
+        self.status = self.quick_search_status();
+    }
+
+    /// Toggle collapsing the table down to only the rows matching the active
+    /// quick search, like `grep`; see [`App::matches_only`]. No-op if no
+    /// quick search is active.
+    fn toggle_matches_only(&mut self) {
+        if self.state.quick_search.is_none() {
+            self.status = "No active search to collapse to".to_string();
+            return;
+        }
+        self.matches_only = !self.matches_only;
+        self.refilter();
+        self.status = if self.matches_only {
+            format!(
+                "Matches only: {} of {}",
+                self.display.rows.len(),
+                self.state.filtered_indices.len()
+            )
+        } else {
+            "Matches only off".to_string()
+        };
+    }
+
+    /// Handle a key event while typing a filter expression opened with `f`.
+    /// Each keystroke re-parses `input_buffer`: on success the expression is
+    /// scheduled to apply after `FILTER_EXPR_DEBOUNCE` (see [`App::tick`]),
+    /// on failure the message is shown inline and nothing new is scheduled.
+    /// `Enter` commits immediately; `Esc` discards the edit.
+    pub fn filter_expr_input(&mut self, key: KeyEvent) {
+        self.dirty = true;
+        if self.handle_input_editing_key(key) {
+            self.reparse_filter_expr_input();
+            return;
+        }
+        match key.code {
+            KeyCode::Esc => {
+                self.pending_filter_expr = None;
+                self.filter_expr_error = None;
+                self.input_buffer.clear();
+                self.input_cursor = 0;
+                self.mode = Mode::Normal;
+                return;
+            }
+            KeyCode::Enter => {
+                let text = std::mem::take(&mut self.input_buffer);
+                self.input_cursor = 0;
+                self.pending_filter_expr = None;
+                self.commit_filter_expr(&text);
+                self.mode = Mode::Normal;
+                return;
+            }
+            KeyCode::Backspace => self.input_backspace(),
+            KeyCode::Char(c) => self.input_insert(c),
+            _ => return,
+        }
+        self.reparse_filter_expr_input();
+    }
+
+    /// Re-parse `input_buffer` as a filter expression after an edit: on
+    /// success, schedule it to apply after `FILTER_EXPR_DEBOUNCE` (see
+    /// [`App::tick`]); on failure, show the error inline and drop anything
+    /// that was scheduled.
+    fn reparse_filter_expr_input(&mut self) {
+        match FilterExpr::parse(&self.input_buffer) {
+            Ok(_) => {
+                self.filter_expr_error = None;
+                self.pending_filter_expr = Some((self.input_buffer.clone(), Instant::now()));
+            }
+            Err(err) => {
+                self.filter_expr_error = Some(err);
+                self.pending_filter_expr = None;
+            }
+        }
+    }
+
+    /// Parse and apply `text` as the active filter expression, clearing it
+    /// (and the row-level filter it drives) if `text` is blank or invalid.
+    fn commit_filter_expr(&mut self, text: &str) {
+        let trimmed = text.trim();
+        self.state.filter.expr = if trimmed.is_empty() {
+            None
+        } else {
+            FilterExpr::parse(trimmed).ok()
+        };
+        self.filter_expr_text = trimmed.to_string();
+        self.refilter();
+        self.status = if trimmed.is_empty() {
+            "Filter: none".to_string()
+        } else {
+            format!("Filter: {trimmed}")
+        };
+    }
+
+    /// Index into `presets` currently highlighted in [`Mode::PresetPicker`].
+    pub fn preset_picker_selected(&self) -> usize {
+        self.preset_picker_selected
+    }
+
+    /// Open the `F` preset popup, pre-selecting the currently active preset
+    /// if there is one. No-op (with a status message) if no presets were
+    /// loaded from `~/.config/logcatui/filters.toml`.
+    fn open_preset_picker(&mut self) {
+        if self.presets.is_empty() {
+            self.status = "No presets configured in ~/.config/logcatui/filters.toml".to_string();
+            return;
+        }
+        self.preset_picker_selected = self
+            .active_preset
+            .as_ref()
+            .and_then(|name| self.presets.iter().position(|preset| &preset.name == name))
+            .unwrap_or(0);
+        self.mode = Mode::PresetPicker;
+    }
+
+    /// Handle a key event while browsing [`Mode::PresetPicker`].
+    pub fn preset_picker_input(&mut self, key: KeyEvent) {
+        self.dirty = true;
+        match key.code {
+            KeyCode::Esc => self.mode = Mode::Normal,
+            KeyCode::Up | KeyCode::Char('k') => {
+                self.preset_picker_selected = self.preset_picker_selected.saturating_sub(1);
+            }
+            KeyCode::Down | KeyCode::Char('j') => {
+                self.preset_picker_selected =
+                    (self.preset_picker_selected + 1).min(self.presets.len().saturating_sub(1));
+            }
+            KeyCode::Enter => {
+                self.activate_preset(self.preset_picker_selected);
+                self.mode = Mode::Normal;
+            }
+            _ => {}
+        }
+    }
+
+    /// `histogram_buckets` rows, as computed by the last
+    /// [`App::open_histogram`]; for [`crate::ui::draw_histogram`].
+    pub fn histogram_buckets(&self) -> &[Bucket] {
+        &self.histogram_buckets
+    }
+
+    /// Index into `histogram_buckets` currently highlighted in
+    /// [`Mode::Histogram`].
+    pub fn histogram_selected(&self) -> usize {
+        self.histogram_selected
+    }
+
+    /// Open the `Alt+H` volume-over-time popup, bucketing the whole loaded
+    /// file (not just the currently filtered/visible rows, so it stays a
+    /// useful map of the file regardless of what's filtered out right now).
+    /// Starts with whichever bucket contains the currently selected row.
+    fn open_histogram(&mut self) {
+        self.histogram_buckets = histogram::compute(&self.entries);
+        self.histogram_selected = self
+            .selected_entry()
+            .and_then(|entry| {
+                self.histogram_buckets
+                    .iter()
+                    .rposition(|bucket| bucket.start <= entry.timestamp)
+            })
+            .unwrap_or(0);
+        self.mode = Mode::Histogram;
+    }
+
+    /// Handle a key event while browsing [`Mode::Histogram`].
+    pub fn histogram_input(&mut self, key: KeyEvent) {
+        self.dirty = true;
+        match key.code {
+            KeyCode::Esc => self.mode = Mode::Normal,
+            KeyCode::Left | KeyCode::Char('h') => {
+                self.histogram_selected = self.histogram_selected.saturating_sub(1);
+            }
+            KeyCode::Right | KeyCode::Char('l') => {
+                self.histogram_selected = (self.histogram_selected + 1)
+                    .min(self.histogram_buckets.len().saturating_sub(1));
+            }
+            KeyCode::Enter => {
+                if let Some(bucket) = self.histogram_buckets.get(self.histogram_selected) {
+                    self.jump_to_entry(bucket.first_entry_index);
+                }
+                self.mode = Mode::Normal;
+            }
+            _ => {}
+        }
+    }
+
+    /// `pinned_highlights`, for [`crate::ui::draw_status_bar`] and
+    /// [`crate::ui::draw_pinned_highlights`]; position in this slice is also
+    /// the color slot passed to [`styles::pin_highlight_style`].
+    pub fn pinned_highlights(&self) -> &[String] {
+        &self.pinned_highlights
+    }
+
+    /// Index into `pinned_highlights` currently highlighted in
+    /// [`Mode::PinnedHighlights`].
+    pub fn pinned_selected(&self) -> usize {
+        self.pinned_selected
+    }
+
+    /// `Ctrl-H`: move the active quick search into `pinned_highlights`,
+    /// keeping it highlighted (in its own color, assigned by list position)
+    /// even after a new search replaces it — the "Camera opened" in yellow,
+    /// "Camera closed" in green at once case. A no-op if no search is
+    /// active, or the pattern is already pinned. Clears the active search
+    /// afterward via [`App::run_incremental_search`] the same way `Esc`
+    /// would, freeing `/` for the next term; `n`/`N` and `matches_only`
+    /// still only ever look at the active search, never pinned ones.
+    fn pin_current_search(&mut self) {
+        let Some(pattern) = self.state.quick_search.clone() else {
+            self.status = "No active search to pin".to_string();
+            return;
+        };
+        if !self.pinned_highlights.contains(&pattern) {
+            self.pinned_highlights.push(pattern);
+        }
+        self.run_incremental_search("");
+        self.status = self.pinned_highlights_status();
+    }
+
+    /// `Ctrl-L`: unpin every pinned highlight at once; see
+    /// [`App::pin_current_search`] and [`App::open_pinned_highlights`] for
+    /// unpinning one at a time.
+    fn clear_pinned_highlights(&mut self) {
+        self.pinned_highlights.clear();
+        self.status = "Pinned highlights cleared".to_string();
+    }
+
+    /// `"Pinned: pattern1, pattern2"`, or a note that none are pinned.
+    fn pinned_highlights_status(&self) -> String {
+        if self.pinned_highlights.is_empty() {
+            "No pinned highlights".to_string()
+        } else {
+            format!("Pinned: {}", self.pinned_highlights.join(", "))
+        }
+    }
+
+    /// Open the `Alt+P` popup for unpinning highlights one at a time; see
+    /// [`App::pinned_highlights_input`].
+    fn open_pinned_highlights(&mut self) {
+        self.pinned_selected = 0;
+        self.mode = Mode::PinnedHighlights;
+    }
+
+    /// Handle a key event while browsing [`Mode::PinnedHighlights`].
+    pub fn pinned_highlights_input(&mut self, key: KeyEvent) {
+        self.dirty = true;
+        match key.code {
+            KeyCode::Esc => self.mode = Mode::Normal,
+            KeyCode::Up | KeyCode::Char('k') => {
+                self.pinned_selected = self.pinned_selected.saturating_sub(1);
+            }
+            KeyCode::Down | KeyCode::Char('j') => {
+                self.pinned_selected =
+                    (self.pinned_selected + 1).min(self.pinned_highlights.len().saturating_sub(1));
+            }
+            KeyCode::Enter | KeyCode::Delete | KeyCode::Char('d') => {
+                if self.pinned_selected < self.pinned_highlights.len() {
+                    self.pinned_highlights.remove(self.pinned_selected);
+                    self.pinned_selected = self
+                        .pinned_selected
+                        .min(self.pinned_highlights.len().saturating_sub(1));
+                }
+                self.status = self.pinned_highlights_status();
+                if self.pinned_highlights.is_empty() {
+                    self.mode = Mode::Normal;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Byte-range spans in `message` highlighted by each pinned pattern,
+    /// tagged with that pattern's color slot (its position in
+    /// `pinned_highlights`); see [`styles::pin_highlight_style`] and
+    /// [`crate::display::DisplayData::as_row`]'s `pinned_spans` parameter.
+    pub fn pinned_match_spans(&self, message: &str) -> Vec<(usize, usize, usize)> {
+        self.pinned_highlights
+            .iter()
+            .enumerate()
+            .flat_map(|(slot, pattern)| {
+                search::match_spans(message, pattern)
+                    .into_iter()
+                    .map(move |(start, end)| (start, end, slot))
+            })
+            .collect()
+    }
+
+    /// `bookmarks` in ascending order, as `(entries[index], index)` pairs,
+    /// for [`crate::ui::draw_bookmarks`].
+    pub fn bookmarks(&self) -> Vec<(&LogEntry, usize)> {
+        self.bookmarks
+            .iter()
+            .filter_map(|&index| self.entries.get(index).map(|entry| (entry, index)))
+            .collect()
+    }
+
+    /// Index into [`App::bookmarks`] currently highlighted in
+    /// [`Mode::Bookmarks`].
+    pub fn bookmark_selected(&self) -> usize {
+        self.bookmark_selected
+    }
+
+    /// `M`: open the bookmarks popup, no-op (with a status message) if none
+    /// are set. See [`App::toggle_bookmark`] and [`App::bookmarks_input`].
+    fn open_bookmarks(&mut self) {
+        if self.bookmarks.is_empty() {
+            self.status = "No bookmarks set".to_string();
+            return;
+        }
+        self.bookmark_selected = 0;
+        self.mode = Mode::Bookmarks;
+    }
+
+    /// Handle a key event while browsing [`Mode::Bookmarks`].
+    pub fn bookmarks_input(&mut self, key: KeyEvent) {
+        self.dirty = true;
+        let bookmarks = self.bookmarks();
+        match key.code {
+            KeyCode::Esc => self.mode = Mode::Normal,
+            KeyCode::Up | KeyCode::Char('k') => {
+                self.bookmark_selected = self.bookmark_selected.saturating_sub(1);
+            }
+            KeyCode::Down | KeyCode::Char('j') => {
+                self.bookmark_selected =
+                    (self.bookmark_selected + 1).min(bookmarks.len().saturating_sub(1));
+            }
+            KeyCode::Enter => {
+                if let Some(&(_, index)) = bookmarks.get(self.bookmark_selected) {
+                    self.jump_to_entry(index);
+                }
+                self.mode = Mode::Normal;
+            }
+            KeyCode::Delete | KeyCode::Char('d') => {
+                if let Some(&(_, index)) = bookmarks.get(self.bookmark_selected) {
+                    self.bookmarks.remove(&index);
+                    self.bookmark_selected = self
+                        .bookmark_selected
+                        .min(self.bookmarks.len().saturating_sub(1));
+                }
+                if self.bookmarks.is_empty() {
+                    self.mode = Mode::Normal;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// `Space`/`o`: open the detail popup for the selected row, no-op if
+    /// nothing is selected. See [`App::entry_detail_input`] and
+    /// [`crate::ui::draw_entry_detail`].
+    fn open_entry_detail(&mut self) {
+        if self.selected_entry().is_none() {
+            return;
+        }
+        self.detail_scroll = 0;
+        self.mode = Mode::EntryDetail;
+    }
+
+    /// Lines scrolled down in the detail popup's message; see
+    /// [`App::entry_detail_input`] and [`crate::ui::draw_entry_detail`].
+    pub fn detail_scroll(&self) -> u16 {
+        self.detail_scroll
+    }
+
+    /// Popup [`Rect`] for the `Space`/`o` detail view, centered over
+    /// [`App::table_area`] with a small margin, shared between
+    /// [`App::entry_detail_input`] (to bound scrolling) and
+    /// [`crate::ui::draw_entry_detail`] (to render into).
+    pub(crate) fn detail_popup_rect(&self) -> Rect {
+        let area = self.table_area;
+        let width = area.width.saturating_sub(4).clamp(20, 100);
+        let height = area.height.saturating_sub(4).max(6);
+        Rect {
+            x: area.x + (area.width.saturating_sub(width)) / 2,
+            y: area.y + (area.height.saturating_sub(height)) / 2,
+            width,
+            height,
+        }
+    }
+
+    /// Lines taken up inside `popup` by the timestamp/PID/TID/level/tag
+    /// fields and the blank line separating them from the message, leaving
+    /// the rest of the popup (minus its border) for the scrollable message;
+    /// see [`App::detail_popup_rect`] and [`crate::ui::draw_entry_detail`].
+    pub(crate) fn detail_message_area_height(popup: Rect) -> u16 {
+        const HEADER_LINES: u16 = 6;
+        popup.height.saturating_sub(2 + HEADER_LINES).max(1)
+    }
+
+    /// The selected entry's message, word-wrapped to fit the detail popup's
+    /// current width; see [`App::detail_popup_rect`].
+    pub(crate) fn detail_message_lines(&self) -> Vec<String> {
+        let Some(entry) = self.selected_entry() else {
+            return Vec::new();
+        };
+        let width = self.detail_popup_rect().width.saturating_sub(2).max(1) as usize;
+        create_text(&entry.message, width)
+    }
+
+    /// Byte-range search-match spans in the selected entry's message, for
+    /// [`crate::ui::draw_entry_detail`] to highlight the same way
+    /// [`App::build_row`] does in the table. Empty with no active search,
+    /// same as [`App::build_row`] treats it.
+    pub fn detail_message_search_spans(&self) -> Vec<(usize, usize)> {
+        if self.search_highlight_hidden {
+            return Vec::new();
+        }
+        let Some(entry) = self.selected_entry() else {
+            return Vec::new();
+        };
+        match self.state.quick_search.as_deref() {
+            Some(pattern) if self.state.fuzzy => search::fuzzy_match_spans(&entry.message, pattern),
+            Some(pattern) => search::match_spans(&entry.message, pattern),
+            None => Vec::new(),
+        }
+    }
+
+    /// Handle a key event while viewing [`Mode::EntryDetail`]. `Up`/`Down`
+    /// scroll the message, bounded so it never scrolls past the end of
+    /// [`App::detail_message_lines`].
+    pub fn entry_detail_input(&mut self, key: KeyEvent) {
+        self.dirty = true;
+        match key.code {
+            KeyCode::Esc | KeyCode::Char(' ') | KeyCode::Char('o') => self.mode = Mode::Normal,
+            KeyCode::Up | KeyCode::Char('k') => {
+                self.detail_scroll = self.detail_scroll.saturating_sub(1);
+            }
+            KeyCode::Down | KeyCode::Char('j') => {
+                let message_lines = self.detail_message_lines().len() as u16;
+                let popup_height = Self::detail_message_area_height(self.detail_popup_rect());
+                let max_scroll = message_lines.saturating_sub(popup_height);
+                self.detail_scroll = (self.detail_scroll + 1).min(max_scroll);
+            }
+            _ => {}
+        }
+    }
+
+    /// The [`Row`]s for `display.rows`, for [`crate::ui::draw_table`].
+    /// `message_width`/`tag_width` come from the caller's current layout, so
+    /// a resize is reflected correctly. Reuses last frame's build from
+    /// [`RowCache`] unchanged if nothing that affects row content changed;
+    /// if only the selection moved, rebuilds just the previously and newly
+    /// selected rows instead of the whole visible range; otherwise rebuilds
+    /// everything.
+    pub(crate) fn visible_rows(
+        &mut self,
+        message_width: usize,
+        tag_width: usize,
+    ) -> Vec<Row<'static>> {
+        let selected = self.table.selected();
+        let key = RowCacheKey {
+            source_indices: self.display.source_indices.clone(),
+            message_width,
+            tag_width,
+            zebra_striping: self.zebra_striping,
+            show_delta_column: self.show_delta_column,
+            search_highlight_hidden: self.search_highlight_hidden,
+            fuzzy: self.state.fuzzy,
+            quick_search: self.state.quick_search.clone(),
+            pinned_highlights: self.pinned_highlights.clone(),
+            collapsed_rows: self.collapsed_rows.clone(),
+            message_scroll: self.message_scroll,
+            bookmarks: self.bookmarks.clone(),
+            tag_highlight_enabled: self.tag_highlight_enabled,
+            muted_level_color: self.muted_level_color,
+            selected,
+            current_match_span: self.current_match_span,
+        };
+
+        if let Some(cached_key) = &self.row_cache.key {
+            if *cached_key == key {
+                return self.row_cache.rows.clone();
+            }
+            if cached_key.same_except_selection(&key) {
+                let mut rows = std::mem::take(&mut self.row_cache.rows);
+                for row_index in [cached_key.selected, key.selected].into_iter().flatten() {
+                    if let Some(row) = self.build_row(row_index, tag_width, message_width, selected)
+                    {
+                        if let Some(slot) = rows.get_mut(row_index) {
+                            *slot = row;
+                        }
+                    }
+                }
+                self.row_cache = RowCache {
+                    key: Some(key),
+                    rows: rows.clone(),
+                };
+                return rows;
+            }
+        }
+
+        let rows: Vec<Row<'static>> = (0..self.display.rows.len())
+            .filter_map(|index| self.build_row(index, tag_width, message_width, selected))
+            .collect();
+        self.row_cache = RowCache {
+            key: Some(key),
+            rows: rows.clone(),
+        };
+        rows
+    }
+
+    /// Build the table row for display row `index`, truncating/wrapping the
+    /// tag and message to `tag_width`/`message_width` and overlaying search,
+    /// current-match, and pinned highlight spans; see
+    /// [`crate::display::DisplayData::as_row`].
+    fn build_row(
+        &self,
+        index: usize,
+        tag_width: usize,
+        message_width: usize,
+        selected: Option<usize>,
+    ) -> Option<Row<'static>> {
+        let entry = self.display.rows.get(index)?;
+        let tag = if entry.tag.len() > tag_width {
+            &entry.tag[..tag_width]
+        } else {
+            entry.tag.as_str()
+        };
+        let collapsed_scroll = self
+            .is_row_collapsed(index)
+            .then_some((self.message_scroll, message_width));
+        let message_lines = if collapsed_scroll.is_some() {
+            vec![entry.message.clone()]
+        } else {
+            create_text(&entry.message, message_width)
+        };
+        let search_spans = if self.search_highlight_hidden {
+            Vec::new()
+        } else {
+            match self.state.quick_search.as_deref() {
+                Some(pattern) if self.state.fuzzy => {
+                    search::fuzzy_match_spans(&entry.message, pattern)
+                }
+                Some(pattern) => search::match_spans(&entry.message, pattern),
+                None => Vec::new(),
+            }
+        };
+        let current_span = (Some(index) == selected && !self.search_highlight_hidden)
+            .then(|| self.current_match_span())
+            .flatten();
+        let pinned_spans = self.pinned_match_spans(&entry.message);
+        let highlights = display::RowHighlights {
+            search_spans: &search_spans,
+            current_span,
+            pinned_spans: &pinned_spans,
+        };
+        let source_style = self
+            .display
+            .source_indices
+            .get(index)
+            .and_then(|&source_index| self.merge_sources.get(source_index))
+            .map(|&file_index| crate::styles::pin_highlight_style(file_index));
+        let bookmarked = self
+            .display
+            .source_indices
+            .get(index)
+            .is_some_and(|source_index| self.bookmarks.contains(source_index));
+        let tag_highlighted = self.tag_highlight_enabled
+            && selected
+                .and_then(|selected| self.display.rows.get(selected))
+                .is_some_and(|selected_entry| selected_entry.tag == entry.tag);
+        self.display.as_row(
+            index,
+            tag,
+            &message_lines,
+            &highlights,
+            self.zebra_striping,
+            source_style,
+            self.show_delta_column,
+            bookmarked,
+            collapsed_scroll,
+            tag_highlighted,
+            self.delta_highlight_threshold,
+            self.muted_level_color,
+            self.display_tz.offset(),
+        )
+    }
+
+    /// Select whichever currently-visible row corresponds to `entry_index`
+    /// (an index into `self.entries`), or the first visible row at-or-after
+    /// it if the exact entry is filtered out, or the last visible row if
+    /// `entry_index` is past everything currently visible.
+    fn jump_to_entry(&mut self, entry_index: usize) {
+        let position = self
+            .display
+            .source_indices
+            .iter()
+            .position(|&source| source >= entry_index)
+            .or_else(|| {
+                (!self.display.source_indices.is_empty()).then(|| self.display.rows.len() - 1)
+            });
+        if let Some(position) = position {
+            self.table.select(Some(position));
+            self.current_match_span = 0;
+        }
+    }
+
+    /// `tag_stats` rows, as computed by the last [`App::open_tag_stats`];
+    /// for [`crate::ui::draw_tag_stats`].
+    pub fn tag_stats(&self) -> &[TagStat] {
+        &self.tag_stats
+    }
+
+    /// Index into `tag_stats` currently highlighted in [`Mode::TagStats`].
+    pub fn tag_stats_selected(&self) -> usize {
+        self.tag_stats_selected
+    }
+
+    /// Open the `Alt+S` summary popup, aggregating the whole loaded file by
+    /// tag (not just the currently filtered/visible rows, so it stays a
+    /// useful map of the file regardless of what's filtered out right now).
+    fn open_tag_stats(&mut self) {
+        self.tag_stats = stats::compute(&self.entries);
+        self.tag_stats_selected = 0;
+        self.mode = Mode::TagStats;
+    }
+
+    /// Handle a key event while browsing [`Mode::TagStats`].
+    pub fn tag_stats_input(&mut self, key: KeyEvent) {
+        self.dirty = true;
+        match key.code {
+            KeyCode::Esc => self.mode = Mode::Normal,
+            KeyCode::Up | KeyCode::Char('k') => {
+                self.tag_stats_selected = self.tag_stats_selected.saturating_sub(1);
+            }
+            KeyCode::Down | KeyCode::Char('j') => {
+                self.tag_stats_selected =
+                    (self.tag_stats_selected + 1).min(self.tag_stats.len().saturating_sub(1));
+            }
+            KeyCode::Enter => {
+                if let Some(stat) = self.tag_stats.get(self.tag_stats_selected).cloned() {
+                    self.committed_tag_filters = vec![TagFilter::parse(&stat.tag)];
+                    self.state.filter.tag_filters = self.committed_tag_filters.clone();
+                    self.refilter();
+                    self.status = format!(
+                        "Tag filters: {}",
+                        tag_filter_summary(&self.state.filter.tag_filters)
+                    );
+                }
+                self.mode = Mode::Normal;
+            }
+            _ => {}
+        }
+    }
+
+    /// Apply `presets[index]`'s expression as `state.filter.preset_expr`,
+    /// composing with whatever tag/level/PID/TID/`f`-expression criteria are
+    /// already set rather than replacing them, and report how many
+    /// previously-visible lines it hid.
+    fn activate_preset(&mut self, index: usize) {
+        let Some(preset) = self.presets.get(index).cloned() else {
+            return;
+        };
+        let before = self.display.rows.len();
+        self.state.filter.preset_expr = FilterExpr::parse(&preset.expression).ok();
+        self.refilter();
+        let hidden = before.saturating_sub(self.display.rows.len());
+        self.status = format!("Preset '{}': {hidden} lines hidden", preset.name);
+        self.active_preset = Some(preset.name);
+    }
+}
+
+/// Build the single-term filter expression text for the `Ctrl+X` quick
+/// filter prompt: `field`'s value on `entry`, negated with `!` if `exclude`.
+/// "Message" uses the first whitespace-delimited word, quoted so the
+/// expression parser treats it as one substring term.
+fn quick_filter_expr_text(field: QuickFilterField, exclude: bool, entry: &LogEntry) -> String {
+    let body = match field {
+        QuickFilterField::Pid => format!("pid:{}", entry.pid),
+        QuickFilterField::Tag => format!("tag:{}", entry.tag),
+        QuickFilterField::Level => format!("level={}", entry.level),
+        QuickFilterField::Message => format!(
+            "\"{}\"",
+            entry.message.split_whitespace().next().unwrap_or("")
+        ),
+    };
+    if exclude {
+        format!("!{body}")
+    } else {
+        body
+    }
+}
+
+/// Render active tag filters as e.g. `Activity, !~Network`, or a placeholder
+/// if there aren't any.
+fn tag_filter_summary(filters: &[TagFilter]) -> String {
+    if filters.is_empty() {
+        return "none".to_string();
+    }
+    filters
+        .iter()
+        .map(|f| {
+            format!(
+                "{}{}{}",
+                if f.exclude { "!" } else { "" },
+                if f.fuzzy { "~" } else { "" },
+                f.pattern
+            )
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn load_logfile_reports_missing_file_without_panicking() {
+        let path = Path::new("/nonexistent/does-not-exist.log");
+        let err = load_logfile(path, LogFormat::Auto).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "log file not found: /nonexistent/does-not-exist.log"
+        );
+    }
+
+    /// `load_logfile` reads through [`crate::encoding::decode`], which never
+    /// fails on invalid UTF-8 — it falls back to a lossy or Latin-1 decode
+    /// instead. This exercises that path end to end to confirm a log file
+    /// with a broken byte sequence still loads and parses.
+    #[test]
+    fn load_logfile_loads_a_file_with_invalid_utf8_bytes() {
+        let path = std::env::temp_dir().join(format!(
+            "logcatui-test-invalid-utf8-{}.log",
+            std::process::id()
+        ));
+        let mut line = b"08-10 12:00:00.123 100 200 I Tag: caf\xE9 payload\n".to_vec();
+        line.extend_from_slice(b"08-10 12:00:01.456 100 200 I Tag: second line\n");
+        fs::write(&path, &line).unwrap();
+
+        let result = load_logfile(&path, LogFormat::Auto);
+        fs::remove_file(&path).unwrap();
+
+        let (entries, _, encoding) = result.unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(encoding, "UTF-8 (lossy)");
+        assert!(entries[0].message.contains('\u{FFFD}'));
+    }
+
+    /// A persisted selection near the end of a file bigger than one
+    /// background-loader chunk used to get clamped into place on the very
+    /// first chunk and never revisited (see synth-569): `drain_loader` would
+    /// `.take()` `pending_restore_selection` before the rest of the file was
+    /// loaded, so the restored row stayed frozen while the status bar kept
+    /// counting up. This drives a real multi-chunk background load (several
+    /// times `loader::CHUNK_SIZE`) to confirm the selection tracks the
+    /// target entry all the way to the end instead of sticking wherever the
+    /// first chunk happened to land.
+    #[test]
+    fn restores_selection_across_multiple_loader_chunks() {
+        const LINE_COUNT: usize = 8_000;
+        let path = std::env::temp_dir().join(format!(
+            "logcatui-test-restore-selection-{}.log",
+            std::process::id()
+        ));
+        let mut contents = String::new();
+        for i in 0..LINE_COUNT {
+            contents.push_str(&format!(
+                "08-10 12:00:00.{:03} 100 200 I Tag: line {i}\n",
+                i % 1000
+            ));
+        }
+        fs::write(&path, &contents).unwrap();
+
+        let mut app = App::new(
+            path.clone(),
+            LogFormat::Threadtime,
+            ColumnWidthConfig::default(),
+            500,
+            1000,
+            TzOption::Utc,
+            None,
+            None,
+            None,
+            false,
+            false,
+        )
+        .unwrap();
+        let target = LINE_COUNT - 1;
+        app.pending_restore_selection = Some(target);
+
+        let deadline = Instant::now() + Duration::from_secs(10);
+        while !app.loader.as_ref().unwrap().progress.is_done() {
+            assert!(Instant::now() < deadline, "background load never finished");
+            app.tick();
+            std::thread::sleep(Duration::from_millis(1));
+        }
+        app.tick();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(app.entries.len(), LINE_COUNT);
+        let selected = app.table.selected().unwrap();
+        assert_eq!(app.display.source_indices[selected], target);
+    }
+}
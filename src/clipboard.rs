@@ -0,0 +1,27 @@
+//! Thin wrapper around the system clipboard, isolating `App` from the
+//! underlying `arboard` crate and from platforms where no clipboard is
+//! available (e.g. a bare SSH session without an X11/Wayland display).
+
+use anyhow::{Context, Result};
+
+/// A handle to the system clipboard, opened lazily per copy so a missing
+/// display server doesn't prevent the rest of the app from working.
+pub struct ClipboardContext;
+
+impl ClipboardContext {
+    pub fn set_text(text: impl Into<String>) -> Result<()> {
+        arboard::Clipboard::new()
+            .context("failed to open system clipboard")?
+            .set_text(text.into())
+            .context("failed to write to system clipboard")
+    }
+
+    /// Read the system clipboard, e.g. for pasting into a text input with
+    /// `Ctrl-V`.
+    pub fn get_text() -> Result<String> {
+        arboard::Clipboard::new()
+            .context("failed to open system clipboard")?
+            .get_text()
+            .context("failed to read from system clipboard")
+    }
+}
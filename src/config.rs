@@ -0,0 +1,548 @@
+//! On-disk defaults, read once at startup from
+//! `$HOME/.config/logcatui/config.toml` if present. CLI flags always win
+//! over the file, and the file is entirely optional.
+
+use std::collections::{BTreeSet, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+use crate::styles::{ThemeName, ThemeOverrides};
+
+/// A column width override: a fixed character count, or `auto` to size the
+/// column to the widest value currently loaded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColumnWidth {
+    Auto,
+    Fixed(u16),
+}
+
+impl FromStr for ColumnWidth {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.eq_ignore_ascii_case("auto") {
+            Ok(ColumnWidth::Auto)
+        } else {
+            s.parse::<u16>()
+                .map(ColumnWidth::Fixed)
+                .map_err(|_| format!("`{s}` is not `auto` or a number"))
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for ColumnWidth {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(serde::de::Error::custom)
+    }
+}
+
+/// Per-column width overrides, from `--max-*-width` or the `[columns]`
+/// table of the config file. `None` keeps the built-in default.
+#[derive(Debug, Clone, Copy, Default, Deserialize)]
+pub struct ColumnWidthConfig {
+    pub max_tag_width: Option<ColumnWidth>,
+    pub max_pid_width: Option<ColumnWidth>,
+    pub max_timestamp_width: Option<ColumnWidth>,
+}
+
+impl ColumnWidthConfig {
+    /// Fields set on `self` win; fields left unset fall back to `file`.
+    pub fn or(self, file: ColumnWidthConfig) -> ColumnWidthConfig {
+        ColumnWidthConfig {
+            max_tag_width: self.max_tag_width.or(file.max_tag_width),
+            max_pid_width: self.max_pid_width.or(file.max_pid_width),
+            max_timestamp_width: self.max_timestamp_width.or(file.max_timestamp_width),
+        }
+    }
+}
+
+/// `--tz`: how to display [`crate::log_entry::LogEntry::timestamp`]. Entries
+/// are parsed as a bare [`chrono::NaiveDateTime`] with no timezone recorded
+/// at all (`logcat -v threadtime` never prints one) — `Utc` is this crate's
+/// long-standing assumption that the value can be shown as-is, not a claim
+/// that the device actually recorded in UTC. `Local`/`Fixed` reinterpret
+/// that same assumption as "and the true zone is ours/this offset", shifting
+/// only the displayed string; sorting and filtering stay on the original
+/// value, so this is purely cosmetic. See
+/// [`crate::app::App::display_tz`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TzOption {
+    Utc,
+    Local,
+    Fixed(chrono::FixedOffset),
+}
+
+impl TzOption {
+    /// The offset to add to a stored timestamp before formatting it.
+    pub fn offset(self) -> chrono::Duration {
+        let seconds = match self {
+            TzOption::Utc => 0,
+            TzOption::Local => chrono::Local::now().offset().local_minus_utc(),
+            TzOption::Fixed(offset) => offset.local_minus_utc(),
+        };
+        chrono::Duration::seconds(seconds.into())
+    }
+
+    /// Status bar label, e.g. `"UTC"`, `"Local"`, or `"UTC+05:30"`. `None`
+    /// for the default [`TzOption::Utc`], so sessions that never asked for
+    /// a timezone conversion don't get an extra status bar segment.
+    pub fn label(self) -> Option<String> {
+        match self {
+            TzOption::Utc => None,
+            TzOption::Local => Some("Local".to_string()),
+            TzOption::Fixed(offset) => Some(format!("UTC{offset}")),
+        }
+    }
+}
+
+impl FromStr for TzOption {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.eq_ignore_ascii_case("utc") {
+            Ok(TzOption::Utc)
+        } else if s.eq_ignore_ascii_case("local") {
+            Ok(TzOption::Local)
+        } else {
+            chrono::FixedOffset::from_str(s)
+                .map(TzOption::Fixed)
+                .map_err(|_| format!("`{s}` is not `utc`, `local`, or an offset like `+05:30`"))
+        }
+    }
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct FileConfig {
+    columns: Option<ColumnWidthConfig>,
+    mouse: Option<MouseConfig>,
+    theme: Option<ThemeName>,
+    colors: Option<ThemeOverrides>,
+}
+
+/// One named filter preset from `~/.config/logcatui/filters.toml`, written
+/// with the same expression syntax as the `f` filter bar; see
+/// [`crate::expr::FilterExpr`].
+#[derive(Debug, Clone, Deserialize)]
+pub struct Preset {
+    pub name: String,
+    pub expression: String,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct PresetsFile {
+    #[serde(default, rename = "preset")]
+    presets: Vec<Preset>,
+}
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+struct MouseConfig {
+    #[serde(default = "default_double_click_ms")]
+    double_click_ms: u64,
+}
+
+fn default_double_click_ms() -> u64 {
+    500
+}
+
+/// Load the `[columns]` table from `$HOME/.config/logcatui/config.toml`.
+/// A missing, unreadable, or unparsable file is treated as no overrides.
+pub fn load_column_widths() -> ColumnWidthConfig {
+    read_file_config().columns.unwrap_or_default()
+}
+
+/// Load `mouse.double_click_ms` from `$HOME/.config/logcatui/config.toml`,
+/// defaulting to 500 if unset, missing, or unparsable.
+pub fn load_double_click_ms() -> u64 {
+    read_file_config()
+        .mouse
+        .map_or_else(default_double_click_ms, |mouse| mouse.double_click_ms)
+}
+
+/// Load `theme` from `$HOME/.config/logcatui/config.toml`, `None` if unset,
+/// missing, or unparsable.
+pub fn load_theme() -> Option<ThemeName> {
+    read_file_config().theme
+}
+
+/// Load the `[colors]` table from `$HOME/.config/logcatui/config.toml`:
+/// per-field color overrides layered on top of whichever `theme` is active,
+/// via [`crate::styles::ThemeConfig::with_overrides`]. Empty (no overrides)
+/// if unset, missing, or unparsable.
+pub fn load_theme_overrides() -> ThemeOverrides {
+    read_file_config().colors.unwrap_or_default()
+}
+
+/// Load `/` search history from `~/.local/share/logcatui/search_history`,
+/// one pattern per line, most recent first; see
+/// [`crate::state::State::record_search`]. Missing or unreadable file
+/// yields no history, same as the other loaders here.
+pub fn load_search_history() -> Vec<String> {
+    search_history_path()
+        .and_then(|path| fs::read_to_string(path).ok())
+        .map(|contents| contents.lines().map(str::to_string).collect())
+        .unwrap_or_default()
+}
+
+/// Persist `history` to `~/.local/share/logcatui/search_history`, one
+/// pattern per line, most recent first, so it survives across runs.
+/// Best-effort: a missing `HOME` or an unwritable directory is silently
+/// ignored, same as every loader in this module.
+pub fn save_search_history(history: &[String]) {
+    let Some(path) = search_history_path() else {
+        return;
+    };
+    if let Some(parent) = path.parent() {
+        let _ = fs::create_dir_all(parent);
+    }
+    let _ = fs::write(path, history.join("\n"));
+}
+
+/// Load presets from `~/.config/logcatui/filters.toml`, in file order. A
+/// missing, unreadable, or unparsable file yields no presets, same as the
+/// other loaders here.
+pub fn load_presets() -> Vec<Preset> {
+    presets_path()
+        .and_then(|path| fs::read_to_string(path).ok())
+        .and_then(|contents| toml::from_str::<PresetsFile>(&contents).ok())
+        .map(|file| file.presets)
+        .unwrap_or_default()
+}
+
+/// Everything [`App`](crate::app::App) remembers about one file between
+/// runs, gated behind `--persist-session`; see [`load_session_state`]/
+/// [`save_session_state`]. `file_len`/`file_mtime_secs` aren't restored
+/// into the app — they're only there so a later load can tell the file
+/// hasn't been rewritten or rotated out from under a stale scroll
+/// position/bookmark set.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PersistedSession {
+    pub selected_entry: Option<usize>,
+    /// [`crate::log_entry::LogLevel::as_char`], so this round-trips through
+    /// JSON without `LogLevel` itself needing to derive `Serialize`.
+    pub level_threshold: Option<String>,
+    /// Raw `t`/`T`/`:filter-tag` specs, as accepted by
+    /// [`crate::filter::TagFilter::parse`].
+    pub tag_filters: Vec<String>,
+    pub pid: Option<u32>,
+    pub tid: Option<u32>,
+    pub bookmarks: BTreeSet<usize>,
+    #[serde(default)]
+    pub(crate) file_len: u64,
+    #[serde(default)]
+    pub(crate) file_mtime_secs: i64,
+}
+
+/// Load the session persisted for `path` from
+/// `~/.local/share/logcatui/session_state.json`, if one was saved and
+/// `path`'s size/modification time still match what was recorded when it
+/// was saved. A changed, missing, or unreadable file, or a missing,
+/// unreadable, or corrupt state file, all yield `None` — restoring nothing
+/// is always safe, unlike restoring a scroll position into a file that's
+/// since changed shape.
+pub fn load_session_state(path: &Path) -> Option<PersistedSession> {
+    let metadata = fs::metadata(path).ok()?;
+    let session = read_session_file().remove(&session_key(path)?)?;
+    if session.file_len == metadata.len() && session.file_mtime_secs == mtime_secs(&metadata) {
+        Some(session)
+    } else {
+        None
+    }
+}
+
+/// Persist `session` for `path`, keyed by its canonicalized absolute path,
+/// alongside `path`'s current size/mtime for [`load_session_state`] to
+/// check next time. Best-effort: a missing `HOME`, an unwritable directory,
+/// or a `path` that no longer exists are all silently ignored, same as
+/// every other saver in this module.
+pub fn save_session_state(path: &Path, mut session: PersistedSession) {
+    let Some(key) = session_key(path) else {
+        return;
+    };
+    let Ok(metadata) = fs::metadata(path) else {
+        return;
+    };
+    let Some(state_path) = session_state_path() else {
+        return;
+    };
+    session.file_len = metadata.len();
+    session.file_mtime_secs = mtime_secs(&metadata);
+
+    let mut sessions = read_session_file();
+    sessions.insert(key, session);
+    if let Some(parent) = state_path.parent() {
+        let _ = fs::create_dir_all(parent);
+    }
+    if let Ok(json) = serde_json::to_string_pretty(&sessions) {
+        let _ = fs::write(state_path, json);
+    }
+}
+
+fn mtime_secs(metadata: &fs::Metadata) -> i64 {
+    metadata
+        .modified()
+        .ok()
+        .and_then(|modified| modified.duration_since(std::time::UNIX_EPOCH).ok())
+        .map_or(0, |duration| duration.as_secs() as i64)
+}
+
+fn session_key(path: &Path) -> Option<String> {
+    fs::canonicalize(path)
+        .ok()
+        .map(|absolute| absolute.display().to_string())
+}
+
+fn read_session_file() -> HashMap<String, PersistedSession> {
+    session_state_path()
+        .and_then(|path| fs::read_to_string(path).ok())
+        .and_then(|contents| serde_json::from_str(&contents).ok())
+        .unwrap_or_default()
+}
+
+fn session_state_path() -> Option<PathBuf> {
+    std::env::var_os("HOME")
+        .map(|home| PathBuf::from(home).join(".local/share/logcatui/session_state.json"))
+}
+
+fn read_file_config() -> FileConfig {
+    config_path()
+        .and_then(|path| fs::read_to_string(path).ok())
+        .and_then(|contents| toml::from_str(&contents).ok())
+        .unwrap_or_default()
+}
+
+fn config_path() -> Option<PathBuf> {
+    std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config/logcatui/config.toml"))
+}
+
+fn presets_path() -> Option<PathBuf> {
+    std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config/logcatui/filters.toml"))
+}
+
+fn search_history_path() -> Option<PathBuf> {
+    std::env::var_os("HOME")
+        .map(|home| PathBuf::from(home).join(".local/share/logcatui/search_history"))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use super::*;
+
+    #[test]
+    fn parses_fixed_width() {
+        assert_eq!("30".parse(), Ok(ColumnWidth::Fixed(30)));
+    }
+
+    #[test]
+    fn parses_auto_case_insensitively() {
+        assert_eq!("AUTO".parse(), Ok(ColumnWidth::Auto));
+    }
+
+    #[test]
+    fn rejects_garbage() {
+        assert!(ColumnWidth::from_str("wide").is_err());
+    }
+
+    #[test]
+    fn tz_option_parses_utc_and_local_case_insensitively() {
+        assert_eq!("UTC".parse(), Ok(TzOption::Utc));
+        assert_eq!("local".parse(), Ok(TzOption::Local));
+    }
+
+    #[test]
+    fn tz_option_parses_a_fixed_offset() {
+        assert_eq!(
+            "+05:30".parse(),
+            Ok(TzOption::Fixed(
+                chrono::FixedOffset::east_opt(5 * 3600 + 30 * 60).unwrap()
+            ))
+        );
+    }
+
+    #[test]
+    fn tz_option_rejects_garbage() {
+        assert!(TzOption::from_str("not-a-zone").is_err());
+    }
+
+    #[test]
+    fn tz_option_utc_offset_is_zero() {
+        assert_eq!(TzOption::Utc.offset(), chrono::Duration::zero());
+    }
+
+    #[test]
+    fn tz_option_fixed_offset_matches_the_parsed_offset() {
+        let tz = TzOption::Fixed(chrono::FixedOffset::east_opt(5 * 3600 + 30 * 60).unwrap());
+        assert_eq!(tz.offset(), chrono::Duration::minutes(5 * 60 + 30));
+    }
+
+    #[test]
+    fn tz_option_label_is_none_for_utc_and_named_otherwise() {
+        assert_eq!(TzOption::Utc.label(), None);
+        assert_eq!(TzOption::Local.label(), Some("Local".to_string()));
+        assert_eq!(
+            TzOption::Fixed(chrono::FixedOffset::east_opt(5 * 3600 + 30 * 60).unwrap()).label(),
+            Some("UTC+05:30".to_string())
+        );
+    }
+
+    #[test]
+    fn parses_colors_table_leaving_unset_fields_none() {
+        let toml = r##"
+            [colors]
+            error = "#ff0000"
+            dimmed = "gray"
+        "##;
+        let file: FileConfig = toml::from_str(toml).unwrap();
+        let colors = file.colors.unwrap();
+        assert!(colors.error.is_some());
+        assert!(colors.dimmed.is_some());
+        assert!(colors.info.is_none());
+    }
+
+    #[test]
+    fn double_click_ms_defaults_to_500() {
+        assert_eq!(default_double_click_ms(), 500);
+    }
+
+    #[test]
+    fn parses_presets_file_in_declared_order() {
+        let toml = r#"
+            [[preset]]
+            name = "no-chatty"
+            expression = "!tag:Chatty"
+
+            [[preset]]
+            name = "errors"
+            expression = "level>=E"
+        "#;
+        let file: PresetsFile = toml::from_str(toml).unwrap();
+        assert_eq!(file.presets.len(), 2);
+        assert_eq!(file.presets[0].name, "no-chatty");
+        assert_eq!(file.presets[1].expression, "level>=E");
+    }
+
+    #[test]
+    fn cli_overrides_win_over_file() {
+        let cli = ColumnWidthConfig {
+            max_tag_width: Some(ColumnWidth::Fixed(10)),
+            ..Default::default()
+        };
+        let file = ColumnWidthConfig {
+            max_tag_width: Some(ColumnWidth::Fixed(99)),
+            max_pid_width: Some(ColumnWidth::Auto),
+            max_timestamp_width: None,
+        };
+        let merged = cli.or(file);
+        assert_eq!(merged.max_tag_width, Some(ColumnWidth::Fixed(10)));
+        assert_eq!(merged.max_pid_width, Some(ColumnWidth::Auto));
+        assert_eq!(merged.max_timestamp_width, None);
+    }
+
+    #[test]
+    fn session_key_canonicalizes_the_path() {
+        let path = std::env::temp_dir().join(format!(
+            "logcatui-test-session-key-{}.log",
+            std::process::id()
+        ));
+        fs::write(&path, "line\n").unwrap();
+        let expected = fs::canonicalize(&path).unwrap().display().to_string();
+        let key = session_key(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+        assert_eq!(key, expected);
+    }
+
+    #[test]
+    fn session_key_is_none_for_a_path_that_does_not_exist() {
+        let path = Path::new("/nonexistent/logcatui-test-session-key.log");
+        assert_eq!(session_key(path), None);
+    }
+
+    /// Serializes the tests below, all of which temporarily override `HOME`
+    /// (a process-global) to point `session_state_path` at a scratch
+    /// directory instead of the real `~/.local/share/logcatui`.
+    static HOME_ENV_LOCK: Mutex<()> = Mutex::new(());
+
+    fn with_temp_home<R>(f: impl FnOnce(&Path) -> R) -> R {
+        let _guard = HOME_ENV_LOCK.lock().unwrap();
+        let home = std::env::temp_dir().join(format!(
+            "logcatui-test-home-{}-{:?}",
+            std::process::id(),
+            std::thread::current().id()
+        ));
+        fs::create_dir_all(&home).unwrap();
+        let original_home = std::env::var_os("HOME");
+        std::env::set_var("HOME", &home);
+
+        let result = f(&home);
+
+        match original_home {
+            Some(value) => std::env::set_var("HOME", value),
+            None => std::env::remove_var("HOME"),
+        }
+        let _ = fs::remove_dir_all(&home);
+        result
+    }
+
+    #[test]
+    fn save_and_load_session_state_round_trips() {
+        with_temp_home(|home| {
+            let log_path = home.join("app.log");
+            fs::write(&log_path, "08-10 12:00:00.123 100 200 I Tag: hello\n").unwrap();
+
+            let session = PersistedSession {
+                selected_entry: Some(42),
+                level_threshold: Some("W".to_string()),
+                tag_filters: vec!["t:Camera".to_string()],
+                pid: Some(100),
+                tid: Some(200),
+                bookmarks: BTreeSet::from([1, 5, 9]),
+                file_len: 0,
+                file_mtime_secs: 0,
+            };
+            save_session_state(&log_path, session);
+
+            let restored = load_session_state(&log_path).unwrap();
+            assert_eq!(restored.selected_entry, Some(42));
+            assert_eq!(restored.level_threshold, Some("W".to_string()));
+            assert_eq!(restored.tag_filters, vec!["t:Camera".to_string()]);
+            assert_eq!(restored.pid, Some(100));
+            assert_eq!(restored.tid, Some(200));
+            assert_eq!(restored.bookmarks, BTreeSet::from([1, 5, 9]));
+        });
+    }
+
+    #[test]
+    fn load_session_state_returns_none_when_the_file_has_since_changed() {
+        with_temp_home(|home| {
+            let log_path = home.join("app.log");
+            fs::write(&log_path, "08-10 12:00:00.123 100 200 I Tag: hello\n").unwrap();
+            save_session_state(&log_path, PersistedSession::default());
+
+            fs::write(
+                &log_path,
+                "08-10 12:00:00.123 100 200 I Tag: hello, again\n",
+            )
+            .unwrap();
+
+            assert!(load_session_state(&log_path).is_none());
+        });
+    }
+
+    #[test]
+    fn load_session_state_is_none_when_nothing_was_saved() {
+        with_temp_home(|home| {
+            let log_path = home.join("app.log");
+            fs::write(&log_path, "08-10 12:00:00.123 100 200 I Tag: hello\n").unwrap();
+            assert!(load_session_state(&log_path).is_none());
+        });
+    }
+}
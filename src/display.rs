@@ -0,0 +1,920 @@
+//! The rows actually shown in the table: the filtered/ordered [`LogEntry`]
+//! list plus any spans detected inside the message text that should be
+//! styled independently of the row-level log-level color (e.g. an embedded
+//! `[WARN]` marker, or later, search highlight spans).
+
+use lazy_static::lazy_static;
+use ratatui::style::Style;
+use ratatui::text::{Line, Span};
+use ratatui::widgets::Row;
+use regex::Regex;
+
+use crate::filter::Filter;
+use crate::log_entry::{LogEntry, LogLevel};
+use crate::styles;
+
+lazy_static! {
+    /// Matches embedded level markers such as `[WARN]`, `ERROR:`, `(info)`.
+    static ref LEVEL_PLACEHOLDER: Regex =
+        Regex::new(r"(?i)\b(verbose|debug|info|warn(?:ing)?|error|fatal)\b").unwrap();
+}
+
+fn placeholder_level(word: &str) -> Option<LogLevel> {
+    match word.to_ascii_lowercase().as_str() {
+        "verbose" => Some(LogLevel::Verbose),
+        "debug" => Some(LogLevel::Debug),
+        "info" => Some(LogLevel::Info),
+        "warn" | "warning" => Some(LogLevel::Warn),
+        "error" => Some(LogLevel::Error),
+        "fatal" => Some(LogLevel::Fatal),
+        _ => None,
+    }
+}
+
+/// Byte-range spans of `message` that should be styled for an embedded
+/// level placeholder, in source order.
+fn level_spans(message: &str) -> Vec<(usize, usize, LogLevel)> {
+    LEVEL_PLACEHOLDER
+        .find_iter(message)
+        .filter_map(|m| placeholder_level(m.as_str()).map(|level| (m.start(), m.end(), level)))
+        .collect()
+}
+
+/// Highlight spans to overlay on a row's message in [`DisplayData::as_row`],
+/// bundled together since they're all computed once per visible row in
+/// [`crate::ui::draw_table`] and `as_row` already had enough positional
+/// arguments without them split out further.
+#[derive(Default)]
+pub struct RowHighlights<'a> {
+    pub search_spans: &'a [(usize, usize)],
+    pub current_span: Option<(usize, usize)>,
+    pub pinned_spans: &'a [(usize, usize, usize)],
+}
+
+/// The rows currently shown in the table, after any filtering/searching.
+/// `rows[i]` came from `entries[source_indices[i]]`, so callers that need to
+/// talk about original line numbers (status bar counters, `--print-on-exit`)
+/// can map a display-local row back to its place in the full file.
+pub struct DisplayData {
+    pub rows: Vec<LogEntry>,
+    pub source_indices: Vec<usize>,
+    /// `dimmed[i]` is true when `rows[i]` doesn't match `filter` and is only
+    /// shown for context, i.e. `filter.display_mode` is
+    /// [`crate::filter::DisplayMode::Dim`] and this row would otherwise have
+    /// been hidden. Always all-`false` in `DisplayMode::Hide`, since
+    /// `rows` only ever contains matches there.
+    pub dimmed: Vec<bool>,
+    message_spans: Vec<Vec<(usize, usize, LogLevel)>>,
+}
+
+impl DisplayData {
+    /// Build the visible rows from `entries[i]` for each `i` in `indices`,
+    /// in order (as produced by [`crate::filter::apply`]).
+    pub fn new(entries: &[LogEntry], indices: &[usize], filter: &Filter) -> Self {
+        let rows: Vec<LogEntry> = indices.iter().map(|&i| entries[i].clone()).collect();
+        let message_spans = rows.iter().map(|e| level_spans(&e.message)).collect();
+        let dimmed = rows.iter().map(|entry| !filter.matches(entry)).collect();
+        DisplayData {
+            rows,
+            source_indices: indices.to_vec(),
+            dimmed,
+            message_spans,
+        }
+    }
+
+    /// Build the table row for entry `index`, given its already-truncated
+    /// `tag` and message already wrapped into `lines` by the caller (both
+    /// depend on the current terminal width). Level placeholders detected
+    /// in the message are styled; `highlights.search_spans` (byte ranges
+    /// into the unwrapped message) take priority over them on overlap.
+    /// `highlights.current_span`, if it's one of `search_spans`, is styled
+    /// with [`styles::search_current_style`] instead of the usual
+    /// [`styles::search_highlight_style`], so the match `n`/`N` most recently
+    /// landed on stands out from any others on the same line; see
+    /// [`crate::app::App::jump_to_match`]. `highlights.pinned_spans` (byte
+    /// ranges plus a color slot, from
+    /// [`crate::app::App::pinned_match_spans`]) overlay underneath
+    /// `search_spans`/`current_span`, so several previously searched terms
+    /// can stay highlighted in their own colors at once; see
+    /// [`styles::pin_highlight_style`]. `zebra` applies
+    /// [`styles::zebra_style`]'s background for this row's position when the
+    /// caller has striping enabled; it only sets the background, so it never
+    /// overrides the foreground colors above, the selected-row `REVERSED`
+    /// highlight, or a search-match span's own background. `highlights` only
+    /// ever style the message column — the pid/tid/level cells are always
+    /// plain `Line::raw`, even when a `pid:`/`tid:`-scoped search matched
+    /// them (see [`crate::search::ScopedPattern`]); the tag cell is too,
+    /// except when `tag_highlighted` is set (see below). The
+    /// pid/tid/level/tag columns are always fixed-width with no horizontal
+    /// scroll of their own (see [`crate::ui::draw_table`]); the message
+    /// column is the one exception, via `collapsed_scroll`: `Some((scroll,
+    /// width))` for a collapsed row slices its single line to the `width`
+    /// characters starting at `scroll` (not bytes, so multi-byte text scrolls
+    /// a whole character at a time), with an ellipsis on whichever side is
+    /// clipped; see [`App::scroll_message`](crate::app::App::scroll_message).
+    /// `None` for a wrapped row, which already shows its whole message
+    /// across `lines` regardless of `scroll`. The timestamp cell is the one
+    /// exception besides the message column: `source_style`, when set,
+    /// paints it as a colored left gutter marking which file this row came
+    /// from, for `--merge`; see [`crate::styles::pin_highlight_style`] and
+    /// [`crate::app::App::new_merged`]. `None` outside of `--merge`, where
+    /// there's only ever one source file to begin with. `show_delta`, when
+    /// set, inserts an extra cell after the timestamp showing the time
+    /// elapsed since the previous row in `rows` (`"-"` for the first row,
+    /// millisecond-granularity below one second, e.g. `+3ms`/`+1.500s`
+    /// otherwise), styled in [`styles::delta_highlight_style`] when that gap
+    /// is at least `delta_threshold`; see
+    /// [`crate::app::App::toggle_delta_column`] and
+    /// [`crate::app::App::delta_highlight_threshold`]. `bookmarked`
+    /// prefixes the timestamp cell with a marker glyph in
+    /// [`styles::bookmark_style`]; see [`crate::app::App::toggle_bookmark`].
+    /// `tag_highlighted` paints the Tag cell with
+    /// [`styles::tag_highlight_style`] instead of the usual plain
+    /// `Line::raw`; see [`crate::app::App::toggle_tag_highlight`].
+    /// `muted_level_color` swaps the row's [`styles::level_style`] tint for
+    /// [`styles::muted_level_style`], a dimmer variant of the same color for
+    /// sessions where full-strength coloring on every cell (message text
+    /// included) is too loud; see
+    /// [`crate::app::App::toggle_muted_level_color`]. Has no effect on a
+    /// dimmed (filtered-out-but-shown) row, which already uses
+    /// [`styles::dimmed_style`] regardless. `tz_offset` is added to the
+    /// timestamp before formatting, for `--tz`; see
+    /// [`crate::app::App::display_tz`].
+    #[allow(clippy::too_many_arguments)]
+    pub fn as_row(
+        &self,
+        index: usize,
+        tag: &str,
+        lines: &[String],
+        highlights: &RowHighlights,
+        zebra: bool,
+        source_style: Option<Style>,
+        show_delta: bool,
+        bookmarked: bool,
+        collapsed_scroll: Option<(usize, usize)>,
+        tag_highlighted: bool,
+        delta_threshold: chrono::Duration,
+        muted_level_color: bool,
+        tz_offset: chrono::Duration,
+    ) -> Option<Row<'static>> {
+        let entry = self.rows.get(index)?;
+        let level_spans = self.message_spans.get(index)?;
+        let style = if self.dimmed.get(index).copied().unwrap_or(false) {
+            styles::dimmed_style()
+        } else if muted_level_color {
+            styles::muted_level_style(entry.level)
+        } else {
+            styles::level_style(entry.level)
+        };
+        let style = if zebra {
+            style.patch(styles::zebra_style(index))
+        } else {
+            style
+        };
+
+        let mut offset = 0;
+        let text_lines: Vec<Line> = lines
+            .iter()
+            .map(|line| {
+                let start = offset;
+                let end = start + line.len();
+                // `create_text`'s wrapped lines are contiguous slices of the
+                // original message with nothing inserted between them, so
+                // the next line picks up exactly where this one ended.
+                offset = end;
+                let (visible, visible_start, visible_end, clipped_left, clipped_right) =
+                    match collapsed_scroll {
+                        Some((scroll, width)) => {
+                            let (window, clipped_left, clipped_right, window_start) =
+                                scroll_window(line, scroll, width);
+                            (
+                                window,
+                                start + window_start,
+                                start + window_start + window.len(),
+                                clipped_left,
+                                clipped_right,
+                            )
+                        }
+                        None => (line.as_str(), start, end, false, false),
+                    };
+                let mut styled = styled_line(
+                    visible,
+                    visible_start,
+                    visible_end,
+                    level_spans,
+                    highlights.search_spans,
+                    highlights.current_span,
+                    highlights.pinned_spans,
+                );
+                if clipped_left {
+                    styled.spans.insert(0, Span::raw("\u{2026}"));
+                }
+                if clipped_right {
+                    styled.spans.push(Span::raw("\u{2026}"));
+                }
+                styled
+            })
+            .collect();
+
+        let timestamp_text = (entry.timestamp + tz_offset)
+            .format("%m-%d %H:%M:%S%.f")
+            .to_string();
+        let timestamp_cell = if bookmarked {
+            Line::styled(
+                format!("\u{25cf}{timestamp_text}"),
+                styles::bookmark_style(),
+            )
+        } else {
+            match source_style {
+                Some(source_style) => Line::styled(timestamp_text, source_style),
+                None => Line::raw(timestamp_text),
+            }
+        };
+
+        let mut cells = vec![timestamp_cell.into()];
+        if show_delta {
+            let delta = (index > 0).then(|| entry.timestamp - self.rows[index - 1].timestamp);
+            let text = match delta {
+                Some(delta) if delta < chrono::Duration::seconds(1) => {
+                    format!("+{}ms", delta.num_milliseconds())
+                }
+                Some(delta) => format!("+{:.3}s", delta.num_milliseconds() as f64 / 1000.0),
+                None => "-".to_string(),
+            };
+            let style = if delta.is_some_and(|delta| delta >= delta_threshold) {
+                styles::delta_highlight_style()
+            } else {
+                Style::default()
+            };
+            cells.push(Line::styled(text, style).into());
+        }
+        let tag_cell = if tag_highlighted {
+            Line::styled(tag.to_string(), styles::tag_highlight_style())
+        } else {
+            Line::raw(tag.to_string())
+        };
+        cells.extend([
+            Line::raw(entry.pid.to_string()).into(),
+            Line::raw(entry.tid.to_string()).into(),
+            Line::raw(entry.level.to_string()).into(),
+            tag_cell.into(),
+            ratatui::text::Text::from(text_lines),
+        ]);
+
+        Some(
+            Row::new(cells)
+                .height(lines.len().max(1) as u16)
+                .style(style),
+        )
+    }
+}
+
+/// Map an absolute entry index (into the unfiltered log, as selected before
+/// a filter change) onto the closest still-visible row position in
+/// `source_indices`. Used by [`crate::app::App::refilter`] so the same log
+/// line — or the nearest one still shown — stays selected across filter
+/// changes, rather than always snapping back to the top of the list.
+/// `source_indices` is assumed sorted ascending, as produced by
+/// [`crate::filter::apply`]. Returns `None` for an empty `source_indices`.
+pub fn nearest_row_for_source(source_indices: &[usize], target: usize) -> Option<usize> {
+    let position = source_indices.partition_point(|&source| source < target);
+
+    match (
+        position.checked_sub(1).map(|i| (i, source_indices[i])),
+        source_indices
+            .get(position)
+            .map(|&source| (position, source)),
+    ) {
+        (Some((before_pos, before)), Some((after_pos, after))) => {
+            if target - before <= after - target {
+                Some(before_pos)
+            } else {
+                Some(after_pos)
+            }
+        }
+        (Some((before_pos, _)), None) => Some(before_pos),
+        (None, Some((after_pos, _))) => Some(after_pos),
+        (None, None) => None,
+    }
+}
+
+/// The visible window into a collapsed row's `message` after scrolling it
+/// `scroll` characters (not bytes, so a multi-byte character is never split)
+/// and clamping to `width` characters. Returns the window itself, whether
+/// content is clipped on the left/right (so the caller can add an ellipsis),
+/// and the window's starting byte offset within `message`, so the caller can
+/// translate it back into an absolute offset for [`styled_line`] — keeping
+/// search/level/pinned highlight spans aligned with the text they actually
+/// cover even after the scroll. `scroll` is capped so the message's last
+/// `width - 1` characters (leaving room for a left ellipsis) stay reachable
+/// but no further, rather than letting it scroll past the end into an empty
+/// window; see [`DisplayData::as_row`].
+fn scroll_window(message: &str, scroll: usize, width: usize) -> (&str, bool, bool, usize) {
+    let boundaries: Vec<usize> = message
+        .char_indices()
+        .map(|(i, _)| i)
+        .chain(std::iter::once(message.len()))
+        .collect();
+    let char_count = boundaries.len() - 1;
+    if width == 0 || char_count == 0 {
+        return ("", scroll > 0 && char_count > 0, false, message.len());
+    }
+    if char_count <= width {
+        return (message, false, false, 0);
+    }
+
+    // The message is longer than `width`, so scrolling anywhere but the very
+    // start clips the left, and stopping anywhere but the very end clips the
+    // right. Cap `start_char` so the last `width - 1` characters (leaving
+    // room for the left ellipsis) are reachable but no further — otherwise
+    // scrolling past the end would show nothing at all.
+    let max_start = char_count - (width - 1);
+    let start_char = scroll.min(max_start);
+    let clipped_left = start_char > 0;
+    let budget = width - usize::from(clipped_left);
+    let end_char = (start_char + budget).min(char_count);
+    let clipped_right = end_char < char_count;
+
+    let start_byte = boundaries[start_char];
+    let end_byte = boundaries[end_char];
+    (
+        &message[start_byte..end_byte],
+        clipped_left,
+        clipped_right,
+        start_byte,
+    )
+}
+
+/// Style one wrapped line of the message, splitting it at any level,
+/// search, or pinned-highlight span boundary that falls within
+/// `[line_start, line_end)`. Pinned spans win on overlap with level spans,
+/// search spans win over pinned ones, and `current_span`, if it overlaps,
+/// wins over all of them; see [`DisplayData::as_row`].
+fn styled_line(
+    line: &str,
+    line_start: usize,
+    line_end: usize,
+    level_spans: &[(usize, usize, LogLevel)],
+    search_spans: &[(usize, usize)],
+    current_span: Option<(usize, usize)>,
+    pinned_spans: &[(usize, usize, usize)],
+) -> Line<'static> {
+    let mut boundaries: Vec<usize> = vec![0, line.len()];
+    for &(start, end, _) in level_spans {
+        boundaries.push(
+            start
+                .max(line_start)
+                .min(line_end)
+                .saturating_sub(line_start),
+        );
+        boundaries.push(end.max(line_start).min(line_end).saturating_sub(line_start));
+    }
+    for &(start, end) in search_spans {
+        boundaries.push(
+            start
+                .max(line_start)
+                .min(line_end)
+                .saturating_sub(line_start),
+        );
+        boundaries.push(end.max(line_start).min(line_end).saturating_sub(line_start));
+    }
+    for &(start, end, _) in pinned_spans {
+        boundaries.push(
+            start
+                .max(line_start)
+                .min(line_end)
+                .saturating_sub(line_start),
+        );
+        boundaries.push(end.max(line_start).min(line_end).saturating_sub(line_start));
+    }
+    boundaries.sort_unstable();
+    boundaries.dedup();
+
+    let mut spans = Vec::new();
+    for window in boundaries.windows(2) {
+        let (start, end) = (window[0], window[1]);
+        if start >= end || end > line.len() {
+            continue;
+        }
+        let absolute_start = line_start + start;
+        let absolute_end = line_start + end;
+
+        let in_search = search_spans
+            .iter()
+            .any(|&(s, e)| s < absolute_end && e > absolute_start);
+        let is_current = current_span.is_some_and(|(s, e)| s < absolute_end && e > absolute_start);
+        let pinned_slot = pinned_spans
+            .iter()
+            .find(|&&(s, e, _)| s < absolute_end && e > absolute_start)
+            .map(|&(_, _, slot)| slot);
+        let level = level_spans
+            .iter()
+            .find(|&&(s, e, _)| s < absolute_end && e > absolute_start)
+            .map(|&(_, _, level)| level);
+
+        let style = if is_current {
+            styles::search_current_style()
+        } else if in_search {
+            styles::search_highlight_style()
+        } else if let Some(slot) = pinned_slot {
+            styles::pin_highlight_style(slot)
+        } else if let Some(level) = level {
+            styles::level_style(level)
+        } else {
+            Style::default()
+        };
+
+        spans.push(Span::styled(line[start..end].to_string(), style));
+    }
+    Line::from(spans)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detects_level_placeholder() {
+        let spans = level_spans("connection failed [WARN] retrying");
+        assert_eq!(spans, vec![(19, 23, LogLevel::Warn)]);
+    }
+
+    #[test]
+    fn nearest_row_for_source_finds_exact_match() {
+        let source_indices = vec![1, 3, 5, 7];
+        assert_eq!(nearest_row_for_source(&source_indices, 5), Some(2));
+    }
+
+    #[test]
+    fn nearest_row_for_source_rounds_to_closer_neighbour() {
+        let source_indices = vec![1, 3, 5, 7];
+        // 4 is equidistant from 3 and 5; ties prefer the earlier (lower) row.
+        assert_eq!(nearest_row_for_source(&source_indices, 4), Some(1));
+        // 15 is closer to 20 than to 7.
+        let source_indices = vec![1, 3, 5, 7, 20];
+        assert_eq!(nearest_row_for_source(&source_indices, 15), Some(4));
+    }
+
+    #[test]
+    fn nearest_row_for_source_clamps_past_the_ends() {
+        let source_indices = vec![10, 20, 30];
+        assert_eq!(nearest_row_for_source(&source_indices, 0), Some(0));
+        assert_eq!(nearest_row_for_source(&source_indices, 100), Some(2));
+    }
+
+    #[test]
+    fn nearest_row_for_source_empty_is_none() {
+        assert_eq!(nearest_row_for_source(&[], 5), None);
+    }
+
+    fn entry_with_tag(tag: &str) -> LogEntry {
+        LogEntry {
+            timestamp: chrono::NaiveDateTime::default(),
+            pid: 0,
+            tid: 0,
+            level: LogLevel::Info,
+            tag: tag.to_string(),
+            message: "msg".to_string(),
+            raw: "raw".to_string(),
+        }
+    }
+
+    #[test]
+    fn marks_non_matching_rows_as_dimmed() {
+        use crate::filter::{DisplayMode, TagFilter};
+
+        let entries = vec![entry_with_tag("ActivityManager"), entry_with_tag("Other")];
+        let filter = Filter {
+            tag_filters: vec![TagFilter::parse("Activity")],
+            display_mode: DisplayMode::Dim,
+            ..Default::default()
+        };
+        let indices = crate::filter::apply(&entries, &filter);
+        let display = DisplayData::new(&entries, &indices, &filter);
+        assert_eq!(display.dimmed, vec![false, true]);
+    }
+
+    #[test]
+    fn ignores_messages_without_placeholders() {
+        assert!(level_spans("plain message").is_empty());
+    }
+
+    /// Reproduces `as_row`'s per-line offset bookkeeping directly against
+    /// `styled_line`, for a message that wraps right in the middle of a
+    /// search match, to guard against the offsets drifting line-to-line.
+    #[test]
+    fn search_highlight_survives_a_match_split_across_a_wrap_boundary() {
+        let message = "hello camera world";
+        let lines = crate::text_utils::create_text(message, 9);
+        assert_eq!(lines, vec!["hello cam", "era world"]);
+
+        let search_spans = crate::search::match_spans(message, "camera");
+        assert_eq!(search_spans, vec![(6, 12)]);
+
+        let mut offset = 0;
+        let styled: Vec<Line> = lines
+            .iter()
+            .map(|line| {
+                let start = offset;
+                let end = start + line.len();
+                offset = end;
+                styled_line(line, start, end, &[], &search_spans, None, &[])
+            })
+            .collect();
+
+        let highlighted: Vec<&str> = styled[0]
+            .spans
+            .iter()
+            .filter(|span| span.style.bg == Some(ratatui::style::Color::Yellow))
+            .map(|span| span.content.as_ref())
+            .collect();
+        assert_eq!(highlighted, vec!["cam"]);
+
+        let highlighted: Vec<&str> = styled[1]
+            .spans
+            .iter()
+            .filter(|span| span.style.bg == Some(ratatui::style::Color::Yellow))
+            .map(|span| span.content.as_ref())
+            .collect();
+        assert_eq!(highlighted, vec!["era"]);
+    }
+
+    #[test]
+    fn current_span_stands_out_from_the_other_matches_on_the_line() {
+        let message = "camera camera camera";
+        let search_spans = crate::search::match_spans(message, "camera");
+        assert_eq!(search_spans, vec![(0, 6), (7, 13), (14, 20)]);
+
+        let styled = styled_line(
+            message,
+            0,
+            message.len(),
+            &[],
+            &search_spans,
+            Some((7, 13)),
+            &[],
+        );
+
+        let current: Vec<&str> = styled
+            .spans
+            .iter()
+            .filter(|span| span.style.bg == Some(ratatui::style::Color::Magenta))
+            .map(|span| span.content.as_ref())
+            .collect();
+        assert_eq!(current, vec!["camera"]);
+
+        let regular: Vec<&str> = styled
+            .spans
+            .iter()
+            .filter(|span| span.style.bg == Some(ratatui::style::Color::Yellow))
+            .map(|span| span.content.as_ref())
+            .collect();
+        assert_eq!(regular, vec!["camera", "camera"]);
+    }
+
+    #[test]
+    fn delta_column_omitted_when_not_requested() {
+        let entries = vec![entry_with_tag("Tag")];
+        let filter = Filter::default();
+        let indices = crate::filter::apply(&entries, &filter);
+        let display = DisplayData::new(&entries, &indices, &filter);
+        let highlights = RowHighlights::default();
+
+        let without_delta = display
+            .as_row(
+                0,
+                "Tag",
+                &["msg".to_string()],
+                &highlights,
+                false,
+                None,
+                false,
+                false,
+                None,
+                false,
+                chrono::Duration::seconds(1),
+                false,
+                chrono::Duration::zero(),
+            )
+            .unwrap();
+        let with_delta = display
+            .as_row(
+                0,
+                "Tag",
+                &["msg".to_string()],
+                &highlights,
+                false,
+                None,
+                true,
+                false,
+                None,
+                false,
+                chrono::Duration::seconds(1),
+                false,
+                chrono::Duration::zero(),
+            )
+            .unwrap();
+        assert_ne!(without_delta, with_delta);
+    }
+
+    #[test]
+    fn delta_column_shows_dash_for_the_first_row_and_flags_large_gaps() {
+        let mut first = entry_with_tag("Tag");
+        first.timestamp = chrono::NaiveDateTime::default();
+        let mut second = entry_with_tag("Tag");
+        second.timestamp = first.timestamp + chrono::Duration::milliseconds(1500);
+        let entries = vec![first, second];
+        let filter = Filter::default();
+        let indices = crate::filter::apply(&entries, &filter);
+        let display = DisplayData::new(&entries, &indices, &filter);
+        let highlights = RowHighlights::default();
+
+        let first_row = display
+            .as_row(
+                0,
+                "Tag",
+                &["msg".to_string()],
+                &highlights,
+                false,
+                None,
+                true,
+                false,
+                None,
+                false,
+                chrono::Duration::seconds(1),
+                false,
+                chrono::Duration::zero(),
+            )
+            .unwrap();
+        let expected_first = Row::new(vec![
+            Line::raw(entries_timestamp(&display, 0)).into(),
+            Line::raw("-").into(),
+            Line::raw("0").into(),
+            Line::raw("0").into(),
+            Line::raw("I").into(),
+            Line::raw("Tag").into(),
+            ratatui::text::Text::from(vec![Line::raw("msg")]),
+        ])
+        .height(1)
+        .style(styles::level_style(LogLevel::Info));
+        assert_eq!(first_row, expected_first);
+
+        let second_row = display
+            .as_row(
+                1,
+                "Tag",
+                &["msg".to_string()],
+                &highlights,
+                false,
+                None,
+                true,
+                false,
+                None,
+                false,
+                chrono::Duration::seconds(1),
+                false,
+                chrono::Duration::zero(),
+            )
+            .unwrap();
+        let expected_second = Row::new(vec![
+            Line::raw(entries_timestamp(&display, 1)).into(),
+            Line::styled("+1.500s", styles::delta_highlight_style()).into(),
+            Line::raw("0").into(),
+            Line::raw("0").into(),
+            Line::raw("I").into(),
+            Line::raw("Tag").into(),
+            ratatui::text::Text::from(vec![Line::raw("msg")]),
+        ])
+        .height(1)
+        .style(styles::level_style(LogLevel::Info));
+        assert_eq!(second_row, expected_second);
+    }
+
+    #[test]
+    fn muted_level_color_swaps_the_row_style() {
+        let entries = vec![entry_with_tag("Tag")];
+        let filter = Filter::default();
+        let indices = crate::filter::apply(&entries, &filter);
+        let display = DisplayData::new(&entries, &indices, &filter);
+        let highlights = RowHighlights::default();
+
+        let muted_row = display
+            .as_row(
+                0,
+                "Tag",
+                &["msg".to_string()],
+                &highlights,
+                false,
+                None,
+                false,
+                false,
+                None,
+                false,
+                chrono::Duration::seconds(1),
+                true,
+                chrono::Duration::zero(),
+            )
+            .unwrap();
+        let expected_row = Row::new(vec![
+            Line::raw(entries_timestamp(&display, 0)).into(),
+            Line::raw("0").into(),
+            Line::raw("0").into(),
+            Line::raw("I").into(),
+            Line::raw("Tag").into(),
+            ratatui::text::Text::from(vec![Line::raw("msg")]),
+        ])
+        .height(1)
+        .style(styles::muted_level_style(LogLevel::Info));
+        assert_eq!(muted_row, expected_row);
+    }
+
+    #[test]
+    fn tz_offset_shifts_the_displayed_timestamp() {
+        let entries = vec![entry_with_tag("Tag")];
+        let filter = Filter::default();
+        let indices = crate::filter::apply(&entries, &filter);
+        let display = DisplayData::new(&entries, &indices, &filter);
+        let highlights = RowHighlights::default();
+        let offset = chrono::Duration::hours(5) + chrono::Duration::minutes(30);
+
+        let row = display
+            .as_row(
+                0,
+                "Tag",
+                &["msg".to_string()],
+                &highlights,
+                false,
+                None,
+                false,
+                false,
+                None,
+                false,
+                chrono::Duration::seconds(1),
+                false,
+                offset,
+            )
+            .unwrap();
+        let expected_timestamp = (display.rows[0].timestamp + offset)
+            .format("%m-%d %H:%M:%S%.f")
+            .to_string();
+        let expected_row = Row::new(vec![
+            Line::raw(expected_timestamp).into(),
+            Line::raw("0").into(),
+            Line::raw("0").into(),
+            Line::raw("I").into(),
+            Line::raw("Tag").into(),
+            ratatui::text::Text::from(vec![Line::raw("msg")]),
+        ])
+        .height(1)
+        .style(styles::level_style(LogLevel::Info));
+        assert_eq!(row, expected_row);
+    }
+
+    fn entries_timestamp(display: &DisplayData, index: usize) -> String {
+        display.rows[index]
+            .timestamp
+            .format("%m-%d %H:%M:%S%.f")
+            .to_string()
+    }
+
+    /// Wrapped (uncollapsed) rows have no horizontal scroll of their own —
+    /// the message column always renders full-width, reflowed by
+    /// [`crate::text_utils::create_text`] at the terminal's current width
+    /// (collapsed rows are scrolled instead; see `collapsed_scroll` below
+    /// and [`crate::app::App::scroll_message`]). The nearest analog here is
+    /// a resize changing that width and forcing a re-wrap; this checks
+    /// highlight spans still land on the matched text (not a shifted
+    /// offset) across different widths.
+    #[test]
+    fn highlight_stays_aligned_to_the_match_across_different_wrap_widths() {
+        let message = "connecting to camera service now";
+        let search_spans = crate::search::match_spans(message, "camera");
+        assert_eq!(search_spans, vec![(14, 20)]);
+
+        for width in [10, 15, 20, message.len()] {
+            let lines = crate::text_utils::create_text(message, width);
+            let mut offset = 0;
+            let mut highlighted = String::new();
+            for line in &lines {
+                let start = offset;
+                let end = start + line.len();
+                offset = end;
+                let styled = styled_line(line, start, end, &[], &search_spans, None, &[]);
+                for span in &styled.spans {
+                    if span.style.bg == Some(ratatui::style::Color::Yellow) {
+                        highlighted.push_str(&span.content);
+                    }
+                }
+            }
+            assert_eq!(highlighted, "camera", "misaligned at width {width}");
+        }
+    }
+
+    /// Reproduces the same offset bookkeeping `as_row` does, for a message
+    /// with multi-byte text (Cyrillic, an emoji, CJK) ahead of the match, to
+    /// guard against the highlight landing off-by-a-few-bytes or panicking
+    /// on a non-char-boundary slice; see `search::find_case_insensitive`.
+    #[test]
+    fn search_highlight_survives_multi_byte_text_before_the_match() {
+        for (message, pattern) in [
+            ("привет камера мир", "камера"),
+            ("🚀🚀 camera 🔥", "camera"),
+            ("相机服务 camera 日本語", "camera"),
+        ] {
+            let search_spans = crate::search::match_spans(message, pattern);
+            assert_eq!(search_spans.len(), 1, "message: {message}");
+
+            let lines = crate::text_utils::create_text(message, message.len());
+            let mut offset = 0;
+            let mut highlighted = String::new();
+            for line in &lines {
+                let start = offset;
+                let end = start + line.len();
+                offset = end;
+                let styled = styled_line(line, start, end, &[], &search_spans, None, &[]);
+                for span in &styled.spans {
+                    if span.style.bg == Some(ratatui::style::Color::Yellow) {
+                        highlighted.push_str(&span.content);
+                    }
+                }
+            }
+            assert_eq!(highlighted, pattern, "message: {message}");
+        }
+    }
+
+    #[test]
+    fn scroll_window_clips_neither_side_when_it_all_fits() {
+        let (visible, clipped_left, clipped_right, start_byte) = scroll_window("short", 0, 10);
+        assert_eq!(
+            (visible, clipped_left, clipped_right, start_byte),
+            ("short", false, false, 0)
+        );
+    }
+
+    #[test]
+    fn scroll_window_clips_the_right_side_only() {
+        let (visible, clipped_left, clipped_right, start_byte) = scroll_window("hello world", 0, 6);
+        assert_eq!(
+            (visible, clipped_left, clipped_right, start_byte),
+            ("hello ", false, true, 0)
+        );
+    }
+
+    #[test]
+    fn scroll_window_clips_both_sides_and_reserves_room_for_the_left_ellipsis() {
+        let (visible, clipped_left, clipped_right, start_byte) = scroll_window("hello world", 4, 6);
+        assert_eq!(
+            (visible, clipped_left, clipped_right, start_byte),
+            ("o wor", true, true, 4)
+        );
+    }
+
+    #[test]
+    fn scroll_window_clamps_scroll_past_the_end_to_the_last_characters() {
+        let (visible, clipped_left, clipped_right, _) = scroll_window("hello", 100, 3);
+        assert_eq!((visible, clipped_left, clipped_right), ("lo", true, false));
+    }
+
+    #[test]
+    fn scroll_window_scrolls_by_whole_characters_not_bytes() {
+        let (visible, clipped_left, clipped_right, start_byte) =
+            scroll_window("привет camera", 1, 5);
+        assert_eq!(visible, "ривет".chars().take(4).collect::<String>());
+        assert!(clipped_left);
+        assert!(clipped_right);
+        // "п" is 2 bytes in UTF-8, so scrolling past it lands 2 bytes in.
+        assert_eq!(start_byte, "п".len());
+    }
+
+    /// `as_row`'s per-line offset bookkeeping restarts from `scroll_window`'s
+    /// `start_byte`, not 0, for a collapsed row — this reproduces that so a
+    /// search highlight stays over the matched text after scrolling past it.
+    #[test]
+    fn search_highlight_stays_aligned_after_scrolling_a_collapsed_row() {
+        let message = "hello camera world";
+        let search_spans = crate::search::match_spans(message, "camera");
+        assert_eq!(search_spans, vec![(6, 12)]);
+
+        let (visible, clipped_left, clipped_right, start_byte) = scroll_window(message, 6, 7);
+        assert_eq!(visible, "camera");
+        assert!(clipped_left);
+        assert!(clipped_right);
+
+        let styled = styled_line(
+            visible,
+            start_byte,
+            start_byte + visible.len(),
+            &[],
+            &search_spans,
+            None,
+            &[],
+        );
+        let highlighted: Vec<&str> = styled
+            .spans
+            .iter()
+            .filter(|span| span.style.bg == Some(ratatui::style::Color::Yellow))
+            .map(|span| span.content.as_ref())
+            .collect();
+        assert_eq!(highlighted, vec!["camera"]);
+    }
+}
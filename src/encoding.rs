@@ -0,0 +1,105 @@
+//! Best-effort encoding detection/transcoding for log files that aren't
+//! UTF-8 — e.g. Latin-1 or UTF-16 dumps some older Android tooling still
+//! produces. [`decode`] is the single entry point both
+//! [`crate::app::load_logfile`] and [`crate::loader::spawn`] funnel raw
+//! bytes through before handing text to [`crate::format::LogFormat::parse`].
+
+use encoding_rs::WINDOWS_1252;
+
+/// Fraction of `from_utf8_lossy`'s output that must be replacement
+/// characters before we stop trusting a UTF-8 decode and try something else.
+const REPLACEMENT_CHAR_THRESHOLD: f64 = 0.05;
+
+/// Decode `bytes`, returning the text plus a label naming the encoding used
+/// (`"UTF-8"` for the overwhelmingly common case, for a startup notice
+/// otherwise). Tries, in order: strict UTF-8; then lossy UTF-8 (labelled
+/// `"UTF-8 (lossy)"`) if at most [`REPLACEMENT_CHAR_THRESHOLD`] of its
+/// output is replacement characters; then UTF-16 if a leading BOM says so;
+/// then `windows-1252` (a practical superset of Latin-1) as a last resort,
+/// since every byte sequence decodes under it.
+pub fn decode(bytes: &[u8]) -> (String, &'static str) {
+    if let Ok(text) = std::str::from_utf8(bytes) {
+        return (text.to_string(), "UTF-8");
+    }
+
+    let lossy = String::from_utf8_lossy(bytes);
+    let replacement_ratio =
+        lossy.matches('\u{FFFD}').count() as f64 / lossy.chars().count().max(1) as f64;
+    if replacement_ratio <= REPLACEMENT_CHAR_THRESHOLD {
+        return (lossy.into_owned(), "UTF-8 (lossy)");
+    }
+
+    let utf16 = match bytes {
+        [0xFF, 0xFE, rest @ ..] => utf16_to_string(rest, false).map(|text| (text, "UTF-16LE")),
+        [0xFE, 0xFF, rest @ ..] => utf16_to_string(rest, true).map(|text| (text, "UTF-16BE")),
+        _ => None,
+    };
+    if let Some(result) = utf16 {
+        return result;
+    }
+
+    let (text, _, _) = WINDOWS_1252.decode(bytes);
+    (text.into_owned(), "Latin-1")
+}
+
+/// Decode `bytes` (without a BOM) as UTF-16 in the given byte order. `None`
+/// if the code units aren't valid UTF-16, e.g. an unpaired surrogate or an
+/// odd number of bytes.
+fn utf16_to_string(bytes: &[u8], big_endian: bool) -> Option<String> {
+    if !bytes.len().is_multiple_of(2) {
+        return None;
+    }
+    let units: Vec<u16> = bytes
+        .chunks_exact(2)
+        .map(|pair| {
+            if big_endian {
+                u16::from_be_bytes([pair[0], pair[1]])
+            } else {
+                u16::from_le_bytes([pair[0], pair[1]])
+            }
+        })
+        .collect();
+    String::from_utf16(&units).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decodes_plain_utf8_unchanged() {
+        let (text, label) = decode("hello".as_bytes());
+        assert_eq!(text, "hello");
+        assert_eq!(label, "UTF-8");
+    }
+
+    #[test]
+    fn decodes_utf16_le_with_bom() {
+        let mut bytes = vec![0xFF, 0xFE];
+        for unit in "hi".encode_utf16() {
+            bytes.extend_from_slice(&unit.to_le_bytes());
+        }
+        let (text, label) = decode(&bytes);
+        assert_eq!(text, "hi");
+        assert_eq!(label, "UTF-16LE");
+    }
+
+    #[test]
+    fn decodes_utf16_be_with_bom() {
+        let mut bytes = vec![0xFE, 0xFF];
+        for unit in "hi".encode_utf16() {
+            bytes.extend_from_slice(&unit.to_be_bytes());
+        }
+        let (text, label) = decode(&bytes);
+        assert_eq!(text, "hi");
+        assert_eq!(label, "UTF-16BE");
+    }
+
+    #[test]
+    fn falls_back_to_latin1_for_high_bytes_without_a_bom() {
+        // 0xE9 is "é" in Latin-1/windows-1252, but isn't valid on its own in UTF-8.
+        let (text, label) = decode(&[b'c', b'a', b'f', 0xE9]);
+        assert_eq!(text, "café");
+        assert_eq!(label, "Latin-1");
+    }
+}
@@ -0,0 +1,264 @@
+//! Parser for the free-form filter expression bar opened with `f`: a
+//! sequence of whitespace-separated terms combined with implicit AND, e.g.
+//! `tag:ActivityManager level>=W pid:1234 "some text" !exclude-me`.
+//!
+//! Supported terms:
+//! - `tag:PATTERN` — case-insensitive substring match on the tag
+//! - `level<OP><LETTER>`, where `<OP>` is one of `=`, `>=`, `<=`, `>`, `<`
+//!   and `<LETTER>` is `V`/`D`/`I`/`W`/`E`/`F`
+//! - `pid:NUMBER`, `tid:NUMBER` — exact match
+//! - `"quoted text"` or a bare word — substring match on the message or tag,
+//!   as in [`crate::search`]
+//!
+//! Any term may be prefixed with `!` to negate it. An empty expression
+//! matches everything.
+
+use crate::log_entry::{LogEntry, LogLevel};
+use crate::search;
+
+#[derive(Debug, Clone, Copy)]
+enum Cmp {
+    Eq,
+    Ge,
+    Le,
+    Gt,
+    Lt,
+}
+
+impl Cmp {
+    fn matches(self, actual: LogLevel, target: LogLevel) -> bool {
+        match self {
+            Cmp::Eq => actual == target,
+            Cmp::Ge => actual >= target,
+            Cmp::Le => actual <= target,
+            Cmp::Gt => actual > target,
+            Cmp::Lt => actual < target,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+enum Term {
+    Tag(String),
+    Level(Cmp, LogLevel),
+    Pid(u32),
+    Tid(u32),
+    Text(String),
+}
+
+impl Term {
+    fn matches(&self, entry: &LogEntry) -> bool {
+        match self {
+            Term::Tag(pattern) => entry.tag.to_lowercase().contains(&pattern.to_lowercase()),
+            Term::Level(cmp, level) => cmp.matches(entry.level, *level),
+            Term::Pid(pid) => entry.pid == *pid,
+            Term::Tid(tid) => entry.tid == *tid,
+            Term::Text(pattern) => search::matches(entry, pattern),
+        }
+    }
+}
+
+/// A single raw token from [`tokenize`]: its text with quotes and any
+/// leading `!` stripped, plus whether it was negated or originally quoted.
+struct Token {
+    text: String,
+    negate: bool,
+    quoted: bool,
+}
+
+fn tokenize(input: &str) -> Result<Vec<Token>, String> {
+    let mut tokens = Vec::new();
+    let mut chars = input.chars().peekable();
+    while let Some(&c) = chars.peek() {
+        if c.is_whitespace() {
+            chars.next();
+            continue;
+        }
+        let negate = c == '!';
+        if negate {
+            chars.next();
+        }
+        let mut text = String::new();
+        let quoted = chars.peek() == Some(&'"');
+        if quoted {
+            chars.next();
+            let mut closed = false;
+            for c in chars.by_ref() {
+                if c == '"' {
+                    closed = true;
+                    break;
+                }
+                text.push(c);
+            }
+            if !closed {
+                return Err("unterminated quote".to_string());
+            }
+        } else {
+            while let Some(&c) = chars.peek() {
+                if c.is_whitespace() {
+                    break;
+                }
+                text.push(c);
+                chars.next();
+            }
+        }
+        tokens.push(Token {
+            text,
+            negate,
+            quoted,
+        });
+    }
+    Ok(tokens)
+}
+
+fn parse_cmp(rest: &str) -> Result<(Cmp, &str), String> {
+    for (prefix, cmp) in [
+        (">=", Cmp::Ge),
+        ("<=", Cmp::Le),
+        ("=", Cmp::Eq),
+        (">", Cmp::Gt),
+        ("<", Cmp::Lt),
+    ] {
+        if let Some(rest) = rest.strip_prefix(prefix) {
+            return Ok((cmp, rest));
+        }
+    }
+    Err(format!(
+        "expected a comparison operator after `level`, found `{rest}`"
+    ))
+}
+
+fn parse_term(token: &Token) -> Result<Term, String> {
+    if token.quoted {
+        return Ok(Term::Text(token.text.clone()));
+    }
+    if let Some(pattern) = token.text.strip_prefix("tag:") {
+        return Ok(Term::Tag(pattern.to_string()));
+    }
+    if let Some(pid) = token.text.strip_prefix("pid:") {
+        return pid
+            .parse()
+            .map(Term::Pid)
+            .map_err(|_| format!("`{pid}` is not a valid PID"));
+    }
+    if let Some(tid) = token.text.strip_prefix("tid:") {
+        return tid
+            .parse()
+            .map(Term::Tid)
+            .map_err(|_| format!("`{tid}` is not a valid TID"));
+    }
+    if let Some(rest) = token.text.strip_prefix("level") {
+        let (cmp, rest) = parse_cmp(rest)?;
+        let level: LogLevel = rest
+            .parse()
+            .map_err(|()| format!("`{rest}` is not a log level (V/D/I/W/E/F)"))?;
+        return Ok(Term::Level(cmp, level));
+    }
+    Ok(Term::Text(token.text.clone()))
+}
+
+/// A parsed filter expression, applied as an additional criterion alongside
+/// the tag/level/PID/TID filters in [`crate::filter::Filter`].
+#[derive(Debug, Clone, Default)]
+pub struct FilterExpr {
+    terms: Vec<(bool, Term)>,
+}
+
+impl FilterExpr {
+    /// Parse a filter bar expression. Returns a human-readable message on
+    /// the first malformed term, suitable for showing inline in the bar.
+    pub fn parse(input: &str) -> Result<Self, String> {
+        let terms = tokenize(input)?
+            .iter()
+            .map(|token| parse_term(token).map(|term| (token.negate, term)))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(FilterExpr { terms })
+    }
+
+    pub fn matches(&self, entry: &LogEntry) -> bool {
+        self.terms
+            .iter()
+            .all(|(negate, term)| term.matches(entry) != *negate)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(pid: u32, tid: u32, level: LogLevel, tag: &str, message: &str) -> LogEntry {
+        LogEntry {
+            timestamp: chrono::NaiveDateTime::default(),
+            pid,
+            tid,
+            level,
+            tag: tag.to_string(),
+            message: message.to_string(),
+            raw: "raw".to_string(),
+        }
+    }
+
+    #[test]
+    fn empty_expression_matches_everything() {
+        let expr = FilterExpr::parse("").unwrap();
+        assert!(expr.matches(&entry(1, 1, LogLevel::Verbose, "Tag", "msg")));
+    }
+
+    #[test]
+    fn tag_term_is_case_insensitive_substring() {
+        let expr = FilterExpr::parse("tag:activity").unwrap();
+        assert!(expr.matches(&entry(1, 1, LogLevel::Info, "ActivityManager", "msg")));
+        assert!(!expr.matches(&entry(1, 1, LogLevel::Info, "NetworkPolicy", "msg")));
+    }
+
+    #[test]
+    fn pid_and_tid_terms_match_exactly() {
+        let expr = FilterExpr::parse("pid:100 tid:7").unwrap();
+        assert!(expr.matches(&entry(100, 7, LogLevel::Info, "Tag", "msg")));
+        assert!(!expr.matches(&entry(100, 8, LogLevel::Info, "Tag", "msg")));
+    }
+
+    #[test]
+    fn level_term_supports_comparisons() {
+        let expr = FilterExpr::parse("level>=W").unwrap();
+        assert!(expr.matches(&entry(1, 1, LogLevel::Error, "Tag", "msg")));
+        assert!(!expr.matches(&entry(1, 1, LogLevel::Info, "Tag", "msg")));
+    }
+
+    #[test]
+    fn quoted_text_matches_across_spaces() {
+        let expr = FilterExpr::parse(r#""some text""#).unwrap();
+        assert!(expr.matches(&entry(1, 1, LogLevel::Info, "Tag", "some text here")));
+        assert!(!expr.matches(&entry(1, 1, LogLevel::Info, "Tag", "unrelated")));
+    }
+
+    #[test]
+    fn unterminated_quote_is_a_parse_error() {
+        assert!(FilterExpr::parse(r#""unterminated"#).is_err());
+    }
+
+    #[test]
+    fn negated_bare_word_excludes_matches() {
+        let expr = FilterExpr::parse("!exclude-me").unwrap();
+        assert!(expr.matches(&entry(1, 1, LogLevel::Info, "Tag", "keep this")));
+        assert!(!expr.matches(&entry(1, 1, LogLevel::Info, "Tag", "exclude-me now")));
+    }
+
+    #[test]
+    fn multiple_terms_combine_with_implicit_and() {
+        let expr = FilterExpr::parse("tag:Activity pid:100 level>=W").unwrap();
+        assert!(expr.matches(&entry(100, 1, LogLevel::Error, "ActivityManager", "msg")));
+        assert!(!expr.matches(&entry(100, 1, LogLevel::Info, "ActivityManager", "msg")));
+        assert!(!expr.matches(&entry(200, 1, LogLevel::Error, "ActivityManager", "msg")));
+    }
+
+    #[test]
+    fn invalid_pid_is_a_parse_error() {
+        assert!(FilterExpr::parse("pid:abc").is_err());
+    }
+
+    #[test]
+    fn invalid_level_letter_is_a_parse_error() {
+        assert!(FilterExpr::parse("level>=X").is_err());
+    }
+}
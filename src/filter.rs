@@ -0,0 +1,427 @@
+//! Row-level filtering, independent of the quick-search/highlight logic in
+//! [`crate::search`]. Criteria are added to [`Filter`] as those features
+//! land.
+
+use crate::expr::FilterExpr;
+use crate::fuzzy;
+use crate::log_entry::{LogEntry, LogLevel};
+
+/// One tag filter, stacked with others in `Filter::tag_filters`. Prefixing
+/// the pattern with `~` switches to fzf-style subsequence matching (the same
+/// [`fuzzy::subsequence_positions`] quick search's `~`/Ctrl-F fuzzy mode
+/// uses — see [`crate::search::fuzzy_matches`]) instead of a substring
+/// match; prefixing with `!` makes it an exclude filter (hide matching tags
+/// instead of requiring them).
+#[derive(Debug, Clone)]
+pub struct TagFilter {
+    pub pattern: String,
+    pub fuzzy: bool,
+    pub exclude: bool,
+}
+
+impl TagFilter {
+    /// Parse a `:filter-tag` argument (or a tag typed at the `t`/`T` prompt),
+    /// stripping a leading `!` into the `exclude` flag and a leading `~`
+    /// into the `fuzzy` flag.
+    pub fn parse(input: &str) -> Self {
+        let (exclude, input) = match input.strip_prefix('!') {
+            Some(rest) => (true, rest),
+            None => (false, input),
+        };
+        match input.strip_prefix('~') {
+            Some(rest) => TagFilter {
+                pattern: rest.to_string(),
+                fuzzy: true,
+                exclude,
+            },
+            None => TagFilter {
+                pattern: input.to_string(),
+                fuzzy: false,
+                exclude,
+            },
+        }
+    }
+
+    fn matches(&self, tag: &str) -> bool {
+        if self.fuzzy {
+            fuzzy::subsequence_positions(tag, &self.pattern).is_some()
+        } else {
+            tag.to_lowercase().contains(&self.pattern.to_lowercase())
+        }
+    }
+}
+
+/// Whether non-matching rows are hidden entirely or kept on screen, dimmed,
+/// for context. Toggled with `v`; see [`App::toggle_display_mode`].
+///
+/// [`App::toggle_display_mode`]: crate::app::App::toggle_display_mode
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum DisplayMode {
+    #[default]
+    Hide,
+    Dim,
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct Filter {
+    /// Active tag filters, stacked: an entry must match every include
+    /// filter and no exclude filter.
+    pub tag_filters: Vec<TagFilter>,
+    /// Hide entries below this severity, e.g. `Some(LogLevel::Warn)` keeps
+    /// only Warn, Error, and Fatal.
+    pub level_threshold: Option<LogLevel>,
+    /// Show only entries from this process.
+    pub pid: Option<u32>,
+    /// Show only entries from this thread.
+    pub tid: Option<u32>,
+    /// Free-form expression from the `f` filter bar, applied in addition to
+    /// every other criterion above. See [`crate::expr`].
+    pub expr: Option<FilterExpr>,
+    /// Expression from the active `F` preset (see
+    /// [`crate::app::App::activate_preset`]), applied in addition to `expr`
+    /// so an ad-hoc `f` filter composes with it rather than replacing it.
+    pub preset_expr: Option<FilterExpr>,
+    /// Single-field expression from the `Ctrl+X` quick filter prompt (see
+    /// [`crate::app::App::quick_filter_input`]), applied in addition to every
+    /// other criterion above. Kept separate from `expr`/`preset_expr` so it
+    /// can be toggled off as its own unit with a second `Ctrl+X`.
+    pub quick_filter: Option<FilterExpr>,
+    /// Whether non-matching rows are hidden or kept for context; see
+    /// [`DisplayMode`].
+    pub display_mode: DisplayMode,
+}
+
+impl Filter {
+    /// Whether any criterion is actually active. `display_mode` only makes
+    /// sense to toggle when this is true — with nothing set every row
+    /// matches, so there'd be nothing to dim.
+    pub fn is_active(&self) -> bool {
+        !self.tag_filters.is_empty()
+            || self.level_threshold.is_some()
+            || self.pid.is_some()
+            || self.tid.is_some()
+            || self.expr.is_some()
+            || self.preset_expr.is_some()
+            || self.quick_filter.is_some()
+    }
+
+    pub(crate) fn matches(&self, entry: &LogEntry) -> bool {
+        if let Some(threshold) = self.level_threshold {
+            if entry.level < threshold {
+                return false;
+            }
+        }
+        if let Some(pid) = self.pid {
+            if entry.pid != pid {
+                return false;
+            }
+        }
+        if let Some(tid) = self.tid {
+            if entry.tid != tid {
+                return false;
+            }
+        }
+        if let Some(expr) = &self.expr {
+            if !expr.matches(entry) {
+                return false;
+            }
+        }
+        if let Some(preset_expr) = &self.preset_expr {
+            if !preset_expr.matches(entry) {
+                return false;
+            }
+        }
+        if let Some(quick_filter) = &self.quick_filter {
+            if !quick_filter.matches(entry) {
+                return false;
+            }
+        }
+        self.tag_filters.iter().all(|tag_filter| {
+            let matches = tag_filter.matches(&entry.tag);
+            matches != tag_filter.exclude
+        })
+    }
+}
+
+/// Return the indices (into `entries`) of the rows to display for `filter`.
+///
+/// In [`DisplayMode::Hide`] (the default) this is just the matching rows. In
+/// [`DisplayMode::Dim`] every row is kept, in order, so non-matching ones can
+/// still render (dimmed — see [`crate::display::DisplayData::dimmed`]) for
+/// context; which rows actually match is recomputed there via
+/// [`Filter::matches`].
+///
+/// This is the one place that scans every entry; callers (`App::refilter`,
+/// `App::reload`) only invoke it when a filter actually changes and cache
+/// the result as `App::visible_indices`, so the render loop and search never
+/// pay this cost per frame. `filtering_200k_entries_stays_well_under_a_frame_budget`
+/// below is a timing note confirming a single scan is cheap even at that
+/// scale, which is what makes recomputing on every filter edit acceptable.
+pub fn apply(entries: &[LogEntry], filter: &Filter) -> Vec<usize> {
+    if filter.display_mode == DisplayMode::Dim {
+        return (0..entries.len()).collect();
+    }
+    entries
+        .iter()
+        .enumerate()
+        .filter(|(_, entry)| filter.matches(entry))
+        .map(|(index, _)| index)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fuzzy_prefix_enables_fuzzy_matching() {
+        let filter = TagFilter::parse("~ActivityManag");
+        assert!(filter.fuzzy);
+        assert!(filter.matches("ActivityManager"));
+    }
+
+    #[test]
+    fn fuzzy_prefix_matches_a_short_non_contiguous_subsequence() {
+        // Same `~` affordance as quick search's fuzzy mode (synth-530): a
+        // short partial pattern should match as a subsequence, not require
+        // staying within a fixed edit distance of the whole tag.
+        let filter = TagFilter::parse("~Act");
+        assert!(filter.matches("ActivityManager"));
+    }
+
+    #[test]
+    fn plain_pattern_is_substring_match() {
+        let filter = TagFilter::parse("Activity");
+        assert!(!filter.fuzzy);
+        assert!(filter.matches("ActivityManager"));
+        assert!(!filter.matches("NetworkPolicy"));
+    }
+
+    #[test]
+    fn plain_pattern_is_case_insensitive() {
+        let filter = TagFilter::parse("activity");
+        assert!(filter.matches("ActivityManager"));
+    }
+
+    #[test]
+    fn exclude_prefix_sets_exclude_flag() {
+        let filter = TagFilter::parse("!Network");
+        assert!(filter.exclude);
+        assert!(!filter.fuzzy);
+        assert!(filter.matches("NetworkPolicy"));
+    }
+
+    #[test]
+    fn exclude_and_fuzzy_prefixes_combine() {
+        let filter = TagFilter::parse("!~Network");
+        assert!(filter.exclude);
+        assert!(filter.fuzzy);
+    }
+
+    fn entry_with_tag(tag: &str) -> LogEntry {
+        LogEntry {
+            timestamp: chrono::NaiveDateTime::default(),
+            pid: 0,
+            tid: 0,
+            level: LogLevel::Info,
+            tag: tag.to_string(),
+            message: "msg".to_string(),
+            raw: "raw".to_string(),
+        }
+    }
+
+    #[test]
+    fn stacked_include_filters_require_all_to_match() {
+        let filter = Filter {
+            tag_filters: vec![TagFilter::parse("Activity"), TagFilter::parse("Manager")],
+            ..Default::default()
+        };
+        assert!(filter.matches(&entry_with_tag("ActivityManager")));
+        assert!(!filter.matches(&entry_with_tag("ActivityService")));
+    }
+
+    #[test]
+    fn exclude_filter_hides_matching_tags_even_if_included() {
+        let filter = Filter {
+            tag_filters: vec![TagFilter::parse("Activity"), TagFilter::parse("!Manager")],
+            ..Default::default()
+        };
+        assert!(!filter.matches(&entry_with_tag("ActivityManager")));
+        assert!(filter.matches(&entry_with_tag("ActivityService")));
+    }
+
+    fn entry_with_level(level: LogLevel) -> LogEntry {
+        LogEntry {
+            timestamp: chrono::NaiveDateTime::default(),
+            pid: 0,
+            tid: 0,
+            level,
+            tag: "Tag".to_string(),
+            message: "msg".to_string(),
+            raw: "raw".to_string(),
+        }
+    }
+
+    #[test]
+    fn level_threshold_hides_entries_below_it() {
+        let filter = Filter {
+            level_threshold: Some(LogLevel::Warn),
+            ..Default::default()
+        };
+        assert!(!filter.matches(&entry_with_level(LogLevel::Info)));
+        assert!(filter.matches(&entry_with_level(LogLevel::Warn)));
+        assert!(filter.matches(&entry_with_level(LogLevel::Error)));
+    }
+
+    #[test]
+    fn no_threshold_keeps_every_level() {
+        let filter = Filter::default();
+        assert!(filter.matches(&entry_with_level(LogLevel::Verbose)));
+    }
+
+    fn entry_with_pid_tid(pid: u32, tid: u32) -> LogEntry {
+        LogEntry {
+            timestamp: chrono::NaiveDateTime::default(),
+            pid,
+            tid,
+            level: LogLevel::Info,
+            tag: "Tag".to_string(),
+            message: "msg".to_string(),
+            raw: "raw".to_string(),
+        }
+    }
+
+    #[test]
+    fn pid_filter_hides_other_processes() {
+        let filter = Filter {
+            pid: Some(100),
+            ..Default::default()
+        };
+        assert!(filter.matches(&entry_with_pid_tid(100, 1)));
+        assert!(!filter.matches(&entry_with_pid_tid(200, 1)));
+    }
+
+    #[test]
+    fn tid_filter_hides_other_threads() {
+        let filter = Filter {
+            tid: Some(1),
+            ..Default::default()
+        };
+        assert!(filter.matches(&entry_with_pid_tid(100, 1)));
+        assert!(!filter.matches(&entry_with_pid_tid(100, 2)));
+    }
+
+    #[test]
+    fn hide_mode_applies_only_matching_indices() {
+        let entries = vec![
+            entry_with_pid_tid(100, 1),
+            entry_with_pid_tid(200, 1),
+            entry_with_pid_tid(100, 1),
+        ];
+        let filter = Filter {
+            pid: Some(100),
+            ..Default::default()
+        };
+        assert_eq!(apply(&entries, &filter), vec![0, 2]);
+    }
+
+    #[test]
+    fn dim_mode_keeps_every_index_in_order() {
+        let entries = vec![
+            entry_with_pid_tid(100, 1),
+            entry_with_pid_tid(200, 1),
+            entry_with_pid_tid(100, 1),
+        ];
+        let filter = Filter {
+            pid: Some(100),
+            display_mode: DisplayMode::Dim,
+            ..Default::default()
+        };
+        assert_eq!(apply(&entries, &filter), vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn preset_expr_composes_with_an_ad_hoc_expr() {
+        use crate::expr::FilterExpr;
+
+        let filter = Filter {
+            preset_expr: Some(FilterExpr::parse("tag:Activity").unwrap()),
+            expr: Some(FilterExpr::parse("pid:100").unwrap()),
+            ..Default::default()
+        };
+        let matching = LogEntry {
+            pid: 100,
+            tag: "ActivityManager".to_string(),
+            ..entry_with_tag("ActivityManager")
+        };
+        let wrong_pid = LogEntry {
+            pid: 1,
+            tag: "ActivityManager".to_string(),
+            ..entry_with_tag("ActivityManager")
+        };
+        assert!(filter.matches(&matching));
+        assert!(!filter.matches(&wrong_pid));
+    }
+
+    #[test]
+    fn quick_filter_composes_with_other_criteria() {
+        use crate::expr::FilterExpr;
+
+        let filter = Filter {
+            pid: Some(100),
+            quick_filter: Some(FilterExpr::parse("tag:Activity").unwrap()),
+            ..Default::default()
+        };
+        let matching = LogEntry {
+            pid: 100,
+            ..entry_with_tag("ActivityManager")
+        };
+        let wrong_tag = LogEntry {
+            pid: 100,
+            ..entry_with_tag("NetworkPolicy")
+        };
+        assert!(filter.matches(&matching));
+        assert!(!filter.matches(&wrong_tag));
+    }
+
+    #[test]
+    fn is_active_reflects_any_set_criterion() {
+        assert!(!Filter::default().is_active());
+        assert!(Filter {
+            pid: Some(1),
+            ..Default::default()
+        }
+        .is_active());
+    }
+
+    /// Timing note, not a correctness check: a single `apply` scan over a
+    /// 200k-entry file — the worst case for a freshly-opened dump — should
+    /// finish in single-digit milliseconds, which is what justifies doing it
+    /// once per filter edit rather than maintaining an incrementally-updated
+    /// index. Run explicitly with `cargo test --release -- --ignored --nocapture`.
+    #[test]
+    #[ignore = "timing note, not a correctness check"]
+    fn filtering_200k_entries_stays_well_under_a_frame_budget() {
+        let entries: Vec<LogEntry> = (0..200_000u32)
+            .map(|i| entry_with_pid_tid(i % 50, i % 8))
+            .collect();
+        let filter = Filter {
+            pid: Some(10),
+            ..Default::default()
+        };
+
+        let started = std::time::Instant::now();
+        let indices = apply(&entries, &filter);
+        let elapsed = started.elapsed();
+
+        println!(
+            "filtered {} of {} entries in {elapsed:?}",
+            indices.len(),
+            entries.len()
+        );
+        assert!(
+            elapsed.as_millis() < 100,
+            "filter::apply took {elapsed:?} for 200k entries, expected well under 100ms"
+        );
+    }
+}
@@ -0,0 +1,344 @@
+//! Parsing strategies for the handful of `logcat -v <format>` layouts we
+//! support. [`LogFormat::parse`] dispatches to the one matching strategy;
+//! [`LogFormat::detect`] picks one automatically from sample lines.
+
+use std::fmt;
+
+use chrono::NaiveDateTime;
+use clap::ValueEnum;
+
+use crate::log_entry::{LogEntry, LogLevel};
+
+/// How many non-empty lines to sample when auto-detecting the format.
+const DETECT_SAMPLE_SIZE: usize = 20;
+
+/// Which `logcat -v` layout to parse lines as.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum LogFormat {
+    /// Try each known format against the first few lines and use whichever
+    /// parses the most of them.
+    Auto,
+    /// `MM-DD HH:MM:SS.mmm PID TID LEVEL TAG: message`
+    Threadtime,
+    /// `LEVEL/TAG(PID): message`
+    Brief,
+    /// `MM-DD HH:MM:SS.mmm LEVEL/TAG(PID): message`
+    Time,
+    /// One journal export JSON object per line, as produced by
+    /// `journalctl --output=json`/`--output=json-seq`. Not auto-detected;
+    /// only used for `--journald` streaming, see [`crate::loader::spawn_journald`].
+    #[value(skip)]
+    Journald,
+}
+
+impl LogFormat {
+    /// The concrete (non-[`LogFormat::Auto`]) formats `detect` chooses among.
+    /// [`LogFormat::Journald`] is deliberately excluded: it's only ever
+    /// selected explicitly via `--journald`, never guessed from file content.
+    const CONCRETE: [LogFormat; 3] = [LogFormat::Threadtime, LogFormat::Brief, LogFormat::Time];
+
+    /// `Err(())` just means "didn't match this format" — there's nothing
+    /// more specific to report, so a custom error type would only add
+    /// ceremony for callers.
+    #[allow(clippy::result_unit_err)]
+    pub fn parse(self, line: &str) -> Result<LogEntry, ()> {
+        match self {
+            LogFormat::Auto => LogFormat::Threadtime.parse(line),
+            LogFormat::Threadtime => parse_threadtime(line),
+            LogFormat::Brief => parse_brief(line),
+            LogFormat::Time => parse_time(line),
+            LogFormat::Journald => parse_journald(line),
+        }
+    }
+
+    /// Sample the first [`DETECT_SAMPLE_SIZE`] non-empty lines and return
+    /// whichever concrete format parses the most of them. Falls back to
+    /// [`LogFormat::Threadtime`] if nothing parses well.
+    pub fn detect<'a>(lines: impl Iterator<Item = &'a str>) -> LogFormat {
+        let sample: Vec<&str> = lines
+            .filter(|l| !l.is_empty())
+            .take(DETECT_SAMPLE_SIZE)
+            .collect();
+
+        let mut best = (LogFormat::Threadtime, 0);
+        for format in LogFormat::CONCRETE {
+            let matched = sample
+                .iter()
+                .filter(|line| format.parse(line).is_ok())
+                .count();
+            if matched > best.1 {
+                best = (format, matched);
+            }
+        }
+        best.0
+    }
+}
+
+impl fmt::Display for LogFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            LogFormat::Auto => "auto",
+            LogFormat::Threadtime => "threadtime",
+            LogFormat::Brief => "brief",
+            LogFormat::Time => "time",
+            LogFormat::Journald => "journald",
+        };
+        write!(f, "{name}")
+    }
+}
+
+fn parse_timestamp(date: &str, time: &str) -> Result<NaiveDateTime, ()> {
+    let timestamp_str = format!("1970-{date} {time}");
+    NaiveDateTime::parse_from_str(&timestamp_str, "%Y-%m-%d %H:%M:%S%.f").map_err(|_| ())
+}
+
+/// Splits off the first `n` whitespace-delimited fields of `line`, collapsing
+/// any run of whitespace between them (real `logcat` output right-pads
+/// columns like PID/TID with extra spaces), and returns them alongside
+/// whatever's left after the `n`th field. Unlike `line.splitn(n + 1,
+/// ' ').filter(|p| !p.is_empty())`, the split points are chosen *after*
+/// whitespace is collapsed, so an empty token from a doubled-up space can't
+/// shift a later field into the wrong slot.
+fn split_fields(line: &str, n: usize) -> Option<(Vec<&str>, &str)> {
+    let mut fields = Vec::with_capacity(n);
+    let mut rest = line;
+    for _ in 0..n {
+        rest = rest.trim_start();
+        let end = rest.find(char::is_whitespace).unwrap_or(rest.len());
+        if end == 0 {
+            return None;
+        }
+        fields.push(&rest[..end]);
+        rest = &rest[end..];
+    }
+    Some((fields, rest.trim_start()))
+}
+
+fn parse_threadtime(line: &str) -> Result<LogEntry, ()> {
+    let (fields, rest) = split_fields(line, 5).ok_or(())?;
+    let [date, time, pid, tid, level] = fields[..] else {
+        return Err(());
+    };
+    let pid = pid.parse::<u32>().map_err(|_| ())?;
+    let tid = tid.parse::<u32>().map_err(|_| ())?;
+    let level = level.parse::<LogLevel>()?;
+    let (tag, message) = rest.split_once(": ").unwrap_or((rest, ""));
+
+    Ok(LogEntry {
+        timestamp: parse_timestamp(date, time)?,
+        pid,
+        tid,
+        level,
+        tag: tag.trim().to_string(),
+        message: message.to_string(),
+        raw: line.to_string(),
+    })
+}
+
+/// Parse the `LEVEL/TAG(PID): message` part shared by `brief` and `time`.
+fn parse_level_tag_pid(rest: &str) -> Result<(LogLevel, String, u32, String), ()> {
+    let (level_str, rest) = rest.split_once('/').ok_or(())?;
+    let level = level_str.parse::<LogLevel>()?;
+    let (tag, rest) = rest.split_once('(').ok_or(())?;
+    let (pid_str, rest) = rest.split_once(')').ok_or(())?;
+    let pid = pid_str.trim().parse::<u32>().map_err(|_| ())?;
+    let message = rest
+        .strip_prefix(": ")
+        .unwrap_or(rest.trim_start_matches(':'));
+    Ok((level, tag.to_string(), pid, message.to_string()))
+}
+
+fn parse_brief(line: &str) -> Result<LogEntry, ()> {
+    let (level, tag, pid, message) = parse_level_tag_pid(line)?;
+    Ok(LogEntry {
+        timestamp: NaiveDateTime::default(),
+        pid,
+        tid: 0,
+        level,
+        tag,
+        message,
+        raw: line.to_string(),
+    })
+}
+
+fn parse_time(line: &str) -> Result<LogEntry, ()> {
+    let (fields, rest) = split_fields(line, 2).ok_or(())?;
+    let [date, time] = fields[..] else {
+        return Err(());
+    };
+
+    let (level, tag, pid, message) = parse_level_tag_pid(rest)?;
+    Ok(LogEntry {
+        timestamp: parse_timestamp(date, time)?,
+        pid,
+        tid: 0,
+        level,
+        tag,
+        message,
+        raw: line.to_string(),
+    })
+}
+
+/// Read one journal export field as a string, whether `journalctl` emitted
+/// it as a JSON string or a JSON number (both occur across systemd versions).
+fn journald_field(value: &serde_json::Value, name: &str) -> Option<String> {
+    let field = value.get(name)?;
+    field
+        .as_str()
+        .map(str::to_string)
+        .or_else(|| field.as_i64().map(|n| n.to_string()))
+}
+
+/// Maps a syslog `PRIORITY` (0 `emerg` through 7 `debug`) onto [`LogLevel`],
+/// since journald has no direct equivalent of logcat's levels.
+fn level_from_priority(priority: u8) -> LogLevel {
+    match priority {
+        0..=2 => LogLevel::Fatal, // emerg, alert, crit
+        3 => LogLevel::Error,     // err
+        4 => LogLevel::Warn,      // warning
+        5 | 6 => LogLevel::Info,  // notice, info
+        _ => LogLevel::Debug,     // debug
+    }
+}
+
+/// Parses a single journal export JSON object, as produced by
+/// `journalctl --output=json`/`--output=json-seq` (one object per line; the
+/// caller is responsible for splitting `json-seq`'s `\x1e`-delimited stream
+/// into individual lines first, see [`crate::loader::spawn_journald`]).
+fn parse_journald(line: &str) -> Result<LogEntry, ()> {
+    let value: serde_json::Value = serde_json::from_str(line.trim()).map_err(|_| ())?;
+
+    let realtime_us: i64 = journald_field(&value, "__REALTIME_TIMESTAMP")
+        .and_then(|s| s.parse().ok())
+        .ok_or(())?;
+    let timestamp = chrono::DateTime::from_timestamp(
+        realtime_us / 1_000_000,
+        ((realtime_us % 1_000_000) * 1_000) as u32,
+    )
+    .ok_or(())?
+    .naive_utc();
+
+    let pid = journald_field(&value, "_PID")
+        .and_then(|s| s.parse().ok())
+        .unwrap_or(0);
+    let tid = journald_field(&value, "_TID")
+        .and_then(|s| s.parse().ok())
+        .unwrap_or(pid);
+    let priority = journald_field(&value, "PRIORITY")
+        .and_then(|s| s.parse().ok())
+        .unwrap_or(6);
+    let tag = journald_field(&value, "SYSLOG_IDENTIFIER")
+        .or_else(|| journald_field(&value, "_COMM"))
+        .unwrap_or_default();
+    let message = journald_field(&value, "MESSAGE").ok_or(())?;
+
+    Ok(LogEntry {
+        timestamp,
+        pid,
+        tid,
+        level: level_from_priority(priority),
+        tag,
+        message,
+        raw: line.to_string(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_threadtime() {
+        let line = "08-08 10:00:00.123 100 200 I MyTag: hello";
+        let entry = LogFormat::Threadtime.parse(line).unwrap();
+        assert_eq!(entry.pid, 100);
+        assert_eq!(entry.tid, 200);
+        assert_eq!(entry.tag, "MyTag");
+        assert_eq!(entry.message, "hello");
+        assert_eq!(entry.raw, line);
+    }
+
+    #[test]
+    fn parses_threadtime_with_padded_columns() {
+        let line = "08-08 10:00:00.123  100 200 I MyTag: hello world";
+        let entry = LogFormat::Threadtime.parse(line).unwrap();
+        assert_eq!(entry.pid, 100);
+        assert_eq!(entry.tid, 200);
+        assert_eq!(entry.level, LogLevel::Info);
+        assert_eq!(entry.tag, "MyTag");
+        assert_eq!(entry.message, "hello world");
+    }
+
+    #[test]
+    fn parses_brief() {
+        let entry = LogFormat::Brief.parse("I/MyTag(1234): hello").unwrap();
+        assert_eq!(entry.pid, 1234);
+        assert_eq!(entry.tid, 0);
+        assert_eq!(entry.tag, "MyTag");
+        assert_eq!(entry.message, "hello");
+    }
+
+    #[test]
+    fn parses_time() {
+        let entry = LogFormat::Time
+            .parse("08-08 10:00:00.123 I/MyTag(1234): hello")
+            .unwrap();
+        assert_eq!(entry.pid, 1234);
+        assert_eq!(entry.tag, "MyTag");
+        assert_eq!(entry.message, "hello");
+    }
+
+    #[test]
+    fn parses_time_with_padded_columns() {
+        let entry = LogFormat::Time
+            .parse("08-08 10:00:00.123  I/MyTag(1234): hello")
+            .unwrap();
+        assert_eq!(entry.pid, 1234);
+        assert_eq!(entry.tag, "MyTag");
+        assert_eq!(entry.message, "hello");
+    }
+
+    #[test]
+    fn detects_threadtime() {
+        let lines = ["08-08 10:00:00.123 100 200 I MyTag: hello"; 3];
+        assert_eq!(LogFormat::detect(lines.into_iter()), LogFormat::Threadtime);
+    }
+
+    #[test]
+    fn detects_brief() {
+        let lines = ["I/MyTag(1234): hello"; 3];
+        assert_eq!(LogFormat::detect(lines.into_iter()), LogFormat::Brief);
+    }
+
+    #[test]
+    fn falls_back_to_threadtime_when_nothing_parses() {
+        let lines = ["not a log line"; 3];
+        assert_eq!(LogFormat::detect(lines.into_iter()), LogFormat::Threadtime);
+    }
+
+    #[test]
+    fn parses_journald_record() {
+        let line = r#"{"__REALTIME_TIMESTAMP":"1700000000000000","_PID":"1234","_TID":"1235","PRIORITY":"3","SYSLOG_IDENTIFIER":"sshd","MESSAGE":"connection refused"}"#;
+        let entry = LogFormat::Journald.parse(line).unwrap();
+        assert_eq!(entry.pid, 1234);
+        assert_eq!(entry.tid, 1235);
+        assert_eq!(entry.level, LogLevel::Error);
+        assert_eq!(entry.tag, "sshd");
+        assert_eq!(entry.message, "connection refused");
+    }
+
+    #[test]
+    fn journald_falls_back_to_comm_and_defaults_missing_tid_to_pid() {
+        let line = r#"{"__REALTIME_TIMESTAMP":"1700000000000000","_PID":"1","PRIORITY":"6","_COMM":"systemd","MESSAGE":"started unit"}"#;
+        let entry = LogFormat::Journald.parse(line).unwrap();
+        assert_eq!(entry.tid, 1);
+        assert_eq!(entry.level, LogLevel::Info);
+        assert_eq!(entry.tag, "systemd");
+    }
+
+    #[test]
+    fn rejects_journald_record_without_message() {
+        let line = r#"{"__REALTIME_TIMESTAMP":"1700000000000000","_PID":"1","PRIORITY":"6"}"#;
+        assert!(LogFormat::Journald.parse(line).is_err());
+    }
+}
@@ -0,0 +1,62 @@
+//! fzf-style subsequence fuzzy matching, shared by the fuzzy tag filter and
+//! fuzzy quick search so the same `~` affordance means the same thing in
+//! both places.
+
+/// fzf-style subsequence match: every character of `pattern` must occur in
+/// `text`, in order, but not necessarily contiguously, case-insensitively.
+/// Returns one byte-range span per matched character (the earliest position
+/// each can match at, since ranking isn't needed — callers only care about
+/// membership and where to highlight), or `None` if `pattern` doesn't occur
+/// as a subsequence at all, or is empty. Used by
+/// [`crate::search::fuzzy_matches`]/[`crate::search::fuzzy_match_spans`] for
+/// the quick-search fuzzy mode, and by [`crate::filter::TagFilter`]'s `~`
+/// fuzzy tag matching, so both `~` affordances behave the same way.
+pub fn subsequence_positions(text: &str, pattern: &str) -> Option<Vec<(usize, usize)>> {
+    if pattern.is_empty() {
+        return None;
+    }
+    let pattern_chars: Vec<char> = pattern.to_lowercase().chars().collect();
+    let mut positions = Vec::with_capacity(pattern_chars.len());
+    let mut chars = text.char_indices();
+    'pattern: for &pc in &pattern_chars {
+        for (start, c) in chars.by_ref() {
+            if c.to_lowercase().eq(std::iter::once(pc)) {
+                positions.push((start, start + c.len_utf8()));
+                continue 'pattern;
+            }
+        }
+        return None;
+    }
+    Some(positions)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn subsequence_matches_non_contiguous_characters_in_order() {
+        let spans = subsequence_positions("Camera opened", "cmr").unwrap();
+        assert_eq!(spans, vec![(0, 1), (2, 3), (4, 5)]);
+    }
+
+    #[test]
+    fn subsequence_is_case_insensitive() {
+        assert!(subsequence_positions("ActivityManager", "AM").is_some());
+    }
+
+    #[test]
+    fn subsequence_rejects_out_of_order_characters() {
+        assert!(subsequence_positions("Camera", "rc").is_none());
+    }
+
+    #[test]
+    fn subsequence_rejects_characters_missing_entirely() {
+        assert!(subsequence_positions("Camera", "cmz").is_none());
+    }
+
+    #[test]
+    fn subsequence_of_empty_pattern_is_none() {
+        assert!(subsequence_positions("Camera", "").is_none());
+    }
+}
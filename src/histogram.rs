@@ -0,0 +1,158 @@
+//! Time-bucketed entry counts for the `Alt+H` histogram popup; see
+//! [`crate::app::App::open_histogram`]. Bucket width adapts to the span of
+//! loaded timestamps, aiming for a fixed number of bars regardless of
+//! whether the file covers seconds or days.
+
+use chrono::{Duration, NaiveDateTime};
+
+use crate::log_entry::LogEntry;
+
+/// Roughly how many bars the popup aims to show.
+const TARGET_BUCKETS: i64 = 60;
+
+/// Bucket widths to pick from, in seconds; the smallest sufficient one wins.
+const WIDTH_STEPS_SECONDS: [i64; 14] = [
+    1,
+    5,
+    10,
+    30,
+    60,
+    300,
+    600,
+    1800,
+    3600,
+    2 * 3600,
+    6 * 3600,
+    12 * 3600,
+    86400,
+    7 * 86400,
+];
+
+/// One bar: its start time (inclusive), how many entries fall in
+/// `[start, start + width)`, and the index into the original `entries`
+/// slice of the chronologically-earliest one of them.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Bucket {
+    pub start: NaiveDateTime,
+    pub count: usize,
+    pub first_entry_index: usize,
+}
+
+/// Bucket `entries` by timestamp into a small, fixed-ish number of bars.
+/// Empty if `entries` is empty.
+pub fn compute(entries: &[LogEntry]) -> Vec<Bucket> {
+    let Some(min) = entries.iter().map(|e| e.timestamp).min() else {
+        return Vec::new();
+    };
+    let max = entries.iter().map(|e| e.timestamp).max().unwrap();
+    let width = bucket_width(max - min);
+    let width_seconds = width.num_seconds().max(1);
+    let bucket_count = ((max - min).num_seconds() / width_seconds) as usize + 1;
+
+    let mut buckets: Vec<Option<(usize, usize, NaiveDateTime)>> = vec![None; bucket_count];
+    for (index, entry) in entries.iter().enumerate() {
+        let offset = ((entry.timestamp - min).num_seconds() / width_seconds) as usize;
+        match &mut buckets[offset] {
+            Some((count, first_index, first_timestamp)) => {
+                *count += 1;
+                if entry.timestamp < *first_timestamp {
+                    *first_index = index;
+                    *first_timestamp = entry.timestamp;
+                }
+            }
+            slot @ None => *slot = Some((1, index, entry.timestamp)),
+        }
+    }
+
+    buckets
+        .into_iter()
+        .enumerate()
+        .map(|(offset, bucket)| {
+            let start = min + width * offset as i32;
+            match bucket {
+                Some((count, first_entry_index, _)) => Bucket {
+                    start,
+                    count,
+                    first_entry_index,
+                },
+                None => Bucket {
+                    start,
+                    count: 0,
+                    first_entry_index: 0,
+                },
+            }
+        })
+        .collect()
+}
+
+/// The smallest step in [`WIDTH_STEPS_SECONDS`] that is at least `span /
+/// TARGET_BUCKETS` seconds (rounded down, minimum 1), or the coarsest step
+/// available if none is that wide. Because the division rounds down, the
+/// bucket count can exceed [`TARGET_BUCKETS`] for short spans (a 119s span
+/// gets 1s buckets, 120 of them).
+fn bucket_width(span: Duration) -> Duration {
+    let target = (span.num_seconds() / TARGET_BUCKETS).max(1);
+    let seconds = WIDTH_STEPS_SECONDS
+        .iter()
+        .copied()
+        .find(|&step| step >= target)
+        .unwrap_or(*WIDTH_STEPS_SECONDS.last().unwrap());
+    Duration::seconds(seconds)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry_at(seconds: i64) -> LogEntry {
+        LogEntry {
+            timestamp: NaiveDateTime::default() + Duration::seconds(seconds),
+            pid: 0,
+            tid: 0,
+            level: crate::log_entry::LogLevel::Info,
+            tag: "T".to_string(),
+            message: "msg".to_string(),
+            raw: "raw".to_string(),
+        }
+    }
+
+    #[test]
+    fn empty_entries_produce_no_buckets() {
+        assert!(compute(&[]).is_empty());
+    }
+
+    #[test]
+    fn a_single_entry_produces_one_bucket() {
+        let buckets = compute(&[entry_at(0)]);
+        assert_eq!(buckets.len(), 1);
+        assert_eq!(buckets[0].count, 1);
+        assert_eq!(buckets[0].first_entry_index, 0);
+    }
+
+    #[test]
+    fn groups_entries_within_the_same_bucket() {
+        // A far-apart fourth entry widens the span enough (400s) that the
+        // adaptive bucket width (10s) comfortably covers the first three.
+        let entries = vec![entry_at(0), entry_at(1), entry_at(2), entry_at(400)];
+        let buckets = compute(&entries);
+        assert_eq!(buckets[0].count, 3);
+    }
+
+    #[test]
+    fn spreads_entries_across_a_wider_span() {
+        let entries = vec![entry_at(0), entry_at(3600)];
+        let buckets = compute(&entries);
+        assert_eq!(buckets.iter().map(|b| b.count).sum::<usize>(), 2);
+        assert!(buckets.len() > 1);
+    }
+
+    #[test]
+    fn first_entry_index_is_the_chronologically_earliest_in_the_bucket() {
+        // A far-apart third entry widens the span enough (400s) that the
+        // adaptive bucket width (10s) puts the first two in one bucket.
+        let entries = vec![entry_at(1), entry_at(0), entry_at(400)];
+        let buckets = compute(&entries);
+        assert_eq!(buckets[0].count, 2);
+        assert_eq!(buckets[0].first_entry_index, 1);
+    }
+}
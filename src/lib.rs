@@ -0,0 +1,29 @@
+//! The logcat parser, filter/search engine, and TUI application state
+//! behind the `logcatui` binary, as a standalone library: parse a dump,
+//! run a quick search, or drive the `App` yourself, without going through
+//! the terminal UI. `main.rs` is a thin binary wrapper around [`app::App`]
+//! and [`ui::draw`].
+
+pub mod app;
+pub mod clipboard;
+pub mod config;
+pub mod display;
+pub mod encoding;
+pub mod expr;
+pub mod filter;
+pub mod format;
+pub mod fuzzy;
+pub mod histogram;
+pub mod loader;
+pub mod log_entry;
+pub mod search;
+pub mod search_worker;
+pub mod state;
+pub mod stats;
+pub mod styles;
+pub mod tabs;
+pub mod text_utils;
+pub mod ui;
+
+pub use app::load_logfile;
+pub use log_entry::{LogEntry, LogLevel};
@@ -0,0 +1,402 @@
+//! Background incremental loader for large log files. [`spawn`] reads and
+//! parses `path` on its own thread into a shared buffer, so the UI becomes
+//! interactive (and can show load progress) before the whole file has been
+//! read, instead of blocking while the whole file is decoded and parsed up
+//! front the way [`crate::app::load_logfile`] does. [`App::tick`](crate::app::App::tick)
+//! drains newly-parsed entries out of the buffer each iteration of the event
+//! loop.
+
+use std::fs::File;
+use std::io::{BufRead, BufReader, Read};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Instant;
+
+use anyhow::{Context, Result};
+
+use crate::encoding;
+use crate::format::LogFormat;
+use crate::log_entry::LogEntry;
+
+/// How many parsed entries accumulate before being handed to the shared
+/// buffer, to keep lock contention with the UI thread low.
+const CHUNK_SIZE: usize = 2000;
+/// How many lines to sample up front (synchronously, on the caller's
+/// thread) to resolve [`LogFormat::Auto`] before the background parse
+/// starts, so the whole file doesn't need to be read just to detect it.
+const DETECT_SAMPLE_LINES: usize = 200;
+/// How many bytes to sample up front to decide whether the file needs
+/// transcoding before parsing, the same way [`DETECT_SAMPLE_LINES`] samples
+/// lines to resolve [`LogFormat::Auto`].
+const ENCODING_SAMPLE_BYTES: usize = 64 * 1024;
+
+/// Resolve `requested` to a concrete format, sampling only the first
+/// [`DETECT_SAMPLE_LINES`] lines of `path` rather than reading it in full.
+/// Opens `path` up front regardless of `requested` so a missing or
+/// unreadable file is reported synchronously here, before [`spawn`] ever
+/// starts its background thread — otherwise a non-`Auto` format would skip
+/// this check entirely and the loader would silently report an empty file.
+pub fn peek_format(path: &Path, requested: LogFormat) -> Result<LogFormat> {
+    let file =
+        File::open(path).with_context(|| format!("failed to open log file {}", path.display()))?;
+    if requested != LogFormat::Auto {
+        return Ok(requested);
+    }
+    let sample: Vec<String> = BufReader::new(file)
+        .lines()
+        .map_while(Result::ok)
+        .take(DETECT_SAMPLE_LINES)
+        .collect();
+    Ok(LogFormat::detect(sample.iter().map(String::as_str)))
+}
+
+/// Progress counters updated by the background thread and read by the UI
+/// thread; all numeric fields are lock-free so reading them never blocks on
+/// a parse in progress. `encoding` is the one exception: it's written once,
+/// before any entries are produced, so the lock is never contended.
+pub struct LoadProgress {
+    bytes_read: AtomicU64,
+    total_bytes: u64,
+    lines_parsed: AtomicU64,
+    done: AtomicBool,
+    started_at: Instant,
+    encoding: Mutex<Option<&'static str>>,
+    /// Set if the background thread couldn't open/read its source at all;
+    /// [`peek_format`] already checks this synchronously for a plain file,
+    /// so in practice this only fires if the file is removed or made
+    /// unreadable in the gap between that check and the thread starting.
+    /// See [`crate::app::App::drain_loader`].
+    error: Mutex<Option<String>>,
+}
+
+impl LoadProgress {
+    /// Percent of the file's bytes consumed so far, or `None` if the file
+    /// size couldn't be determined up front.
+    pub fn percent(&self) -> Option<f64> {
+        if self.total_bytes == 0 {
+            return None;
+        }
+        Some(self.bytes_read.load(Ordering::Relaxed) as f64 / self.total_bytes as f64 * 100.0)
+    }
+
+    /// Total lines handed to `format.parse`, successfully parsed or not;
+    /// `lines_parsed() - entries.len()` once [`LoadProgress::is_done`] is how
+    /// many failed to parse. See [`crate::app::App::trace`].
+    pub fn lines_parsed(&self) -> u64 {
+        self.lines_parsed.load(Ordering::Relaxed)
+    }
+
+    pub fn lines_per_second(&self) -> f64 {
+        let elapsed = self.started_at.elapsed().as_secs_f64();
+        if elapsed <= 0.0 {
+            0.0
+        } else {
+            self.lines_parsed.load(Ordering::Relaxed) as f64 / elapsed
+        }
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.done.load(Ordering::Relaxed)
+    }
+
+    /// The encoding the file was decoded as, once detected, or `None` before
+    /// that (parsing hasn't started, or the source has no file encoding at
+    /// all, e.g. [`spawn_journald`]). `Some("UTF-8")` is still reported, so
+    /// callers can tell "not yet known" from "confirmed plain UTF-8".
+    pub fn encoding(&self) -> Option<&'static str> {
+        *self.encoding.lock().unwrap()
+    }
+
+    /// The error from opening/reading the source, if the background thread
+    /// couldn't get started at all. `None` until [`LoadProgress::is_done`].
+    pub fn error(&self) -> Option<String> {
+        self.error.lock().unwrap().clone()
+    }
+}
+
+/// A parse running on a background thread. `entries` fills in as parsing
+/// progresses, in chunks of [`CHUNK_SIZE`]; the caller is expected to drain
+/// it periodically (see `App::tick`).
+pub struct Loader {
+    pub entries: Arc<Mutex<Vec<LogEntry>>>,
+    pub progress: Arc<LoadProgress>,
+}
+
+/// `true` if `sample` is valid UTF-8, or is a UTF-8 prefix that was merely
+/// cut short mid-character at the sample boundary (so a legitimately-UTF-8
+/// file isn't misdetected just because [`ENCODING_SAMPLE_BYTES`] landed
+/// inside a multi-byte character).
+fn sample_is_utf8(sample: &[u8]) -> bool {
+    match std::str::from_utf8(sample) {
+        Ok(_) => true,
+        Err(err) => err.error_len().is_none() && err.valid_up_to() > 0,
+    }
+}
+
+/// Wrap already-loaded `entries` as a completed [`Loader`], for sources that
+/// have to be read and processed in full up front rather than streamed
+/// incrementally — e.g. `--merge`, which has to see every file before it
+/// can sort them together; see
+/// [`crate::app::App::new_merged`]. Unlike [`spawn`], there's no background
+/// thread here: `entries` is already final, so the loader starts out
+/// reporting [`LoadProgress::is_done`].
+pub fn from_entries(entries: Vec<LogEntry>, encoding: &'static str) -> Loader {
+    let progress = Arc::new(LoadProgress {
+        bytes_read: AtomicU64::new(0),
+        total_bytes: 0,
+        lines_parsed: AtomicU64::new(entries.len() as u64),
+        done: AtomicBool::new(true),
+        started_at: Instant::now(),
+        encoding: Mutex::new(Some(encoding)),
+        error: Mutex::new(None),
+    });
+    Loader {
+        entries: Arc::new(Mutex::new(entries)),
+        progress,
+    }
+}
+
+/// Start reading and parsing `path` as `format` on a background thread.
+/// `format` must already be resolved (not [`LogFormat::Auto`]); see
+/// [`peek_format`].
+pub fn spawn(path: PathBuf, format: LogFormat) -> Loader {
+    let total_bytes = std::fs::metadata(&path).map(|m| m.len()).unwrap_or(0);
+    let entries = Arc::new(Mutex::new(Vec::new()));
+    let progress = Arc::new(LoadProgress {
+        bytes_read: AtomicU64::new(0),
+        total_bytes,
+        lines_parsed: AtomicU64::new(0),
+        done: AtomicBool::new(false),
+        started_at: Instant::now(),
+        encoding: Mutex::new(None),
+        error: Mutex::new(None),
+    });
+
+    let shared_entries = Arc::clone(&entries);
+    let shared_progress = Arc::clone(&progress);
+    thread::spawn(move || {
+        let mut sample = vec![0u8; ENCODING_SAMPLE_BYTES];
+        let sampled = File::open(&path)
+            .and_then(|mut file| file.read(&mut sample))
+            .unwrap_or(0);
+        sample.truncate(sampled);
+
+        if sample_is_utf8(&sample) {
+            *shared_progress.encoding.lock().unwrap() = Some("UTF-8");
+            parse_file_streaming(&path, format, &shared_entries, &shared_progress);
+        } else {
+            parse_file_with_transcoding(&path, format, &shared_entries, &shared_progress);
+        }
+        shared_progress.done.store(true, Ordering::Relaxed);
+    });
+
+    Loader { entries, progress }
+}
+
+/// One-line description of a failure to open/read `path`, the same
+/// categories [`crate::app::load_logfile`] reports for its own eager read.
+fn describe_open_error(path: &Path, err: &std::io::Error) -> String {
+    match err.kind() {
+        std::io::ErrorKind::NotFound => format!("log file not found: {}", path.display()),
+        std::io::ErrorKind::PermissionDenied => {
+            format!("permission denied reading log file: {}", path.display())
+        }
+        _ => format!("failed to open log file {}: {err}", path.display()),
+    }
+}
+
+/// The common case: `path` is already UTF-8, so it can be streamed and
+/// parsed line-by-line without ever holding the whole file in memory.
+fn parse_file_streaming(
+    path: &Path,
+    format: LogFormat,
+    shared_entries: &Mutex<Vec<LogEntry>>,
+    shared_progress: &LoadProgress,
+) {
+    let file = match File::open(path) {
+        Ok(file) => file,
+        Err(err) => {
+            *shared_progress.error.lock().unwrap() = Some(describe_open_error(path, &err));
+            return;
+        }
+    };
+    let mut reader = BufReader::new(file);
+    let mut chunk = Vec::with_capacity(CHUNK_SIZE);
+    let mut line = String::new();
+    loop {
+        line.clear();
+        let read = reader.read_line(&mut line).unwrap_or(0);
+        if read == 0 {
+            break;
+        }
+        shared_progress
+            .bytes_read
+            .fetch_add(read as u64, Ordering::Relaxed);
+        shared_progress.lines_parsed.fetch_add(1, Ordering::Relaxed);
+        if let Ok(entry) = format.parse(line.trim_end_matches(['\r', '\n'])) {
+            chunk.push(entry);
+        }
+        if chunk.len() >= CHUNK_SIZE {
+            shared_entries.lock().unwrap().append(&mut chunk);
+        }
+    }
+    if !chunk.is_empty() {
+        shared_entries.lock().unwrap().append(&mut chunk);
+    }
+}
+
+/// The rare case: `path` isn't UTF-8, so it has to be read and transcoded in
+/// full before parsing can start (unlike [`parse_file_streaming`], which
+/// never holds more than a few lines in memory at once). `total_bytes`
+/// having been sized off the on-disk file means `bytes_read` (counted here
+/// against the transcoded text) is only an approximation of
+/// [`LoadProgress::percent`], but it's close enough for a progress bar.
+fn parse_file_with_transcoding(
+    path: &Path,
+    format: LogFormat,
+    shared_entries: &Mutex<Vec<LogEntry>>,
+    shared_progress: &LoadProgress,
+) {
+    let bytes = match std::fs::read(path) {
+        Ok(bytes) => bytes,
+        Err(err) => {
+            *shared_progress.error.lock().unwrap() = Some(describe_open_error(path, &err));
+            return;
+        }
+    };
+    let (text, label) = encoding::decode(&bytes);
+    *shared_progress.encoding.lock().unwrap() = Some(label);
+
+    let mut chunk = Vec::with_capacity(CHUNK_SIZE);
+    for line in text.lines() {
+        shared_progress
+            .bytes_read
+            .fetch_add(line.len() as u64 + 1, Ordering::Relaxed);
+        shared_progress.lines_parsed.fetch_add(1, Ordering::Relaxed);
+        if let Ok(entry) = format.parse(line) {
+            chunk.push(entry);
+        }
+        if chunk.len() >= CHUNK_SIZE {
+            shared_entries.lock().unwrap().append(&mut chunk);
+        }
+    }
+    if !chunk.is_empty() {
+        shared_entries.lock().unwrap().append(&mut chunk);
+    }
+}
+
+/// Start `journalctl --follow --no-pager --output=json-seq [--unit=unit]`
+/// on a background thread and stream its stdout as [`LogFormat::Journald`]
+/// entries, the same way [`spawn`] streams a file. Since `--follow` never
+/// finishes on its own, `total_bytes` is always 0 here, so
+/// [`LoadProgress::percent`] always reads as unknown rather than stuck at a
+/// wrong number.
+pub fn spawn_journald(unit: Option<&str>) -> Result<Loader> {
+    let mut command = Command::new("journalctl");
+    command
+        .arg("--follow")
+        .arg("--no-pager")
+        .arg("--output=json-seq");
+    if let Some(unit) = unit {
+        command.arg(format!("--unit={unit}"));
+    }
+
+    let mut child = command
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .map_err(|err| match err.kind() {
+            std::io::ErrorKind::NotFound => anyhow::anyhow!(
+                "journalctl not found in PATH; --journald requires a systemd journal to read from"
+            ),
+            _ => anyhow::Error::from(err).context("failed to start journalctl"),
+        })?;
+    let stdout = child
+        .stdout
+        .take()
+        .context("journalctl produced no stdout pipe")?;
+
+    let entries = Arc::new(Mutex::new(Vec::new()));
+    let progress = Arc::new(LoadProgress {
+        bytes_read: AtomicU64::new(0),
+        total_bytes: 0,
+        lines_parsed: AtomicU64::new(0),
+        done: AtomicBool::new(false),
+        started_at: Instant::now(),
+        encoding: Mutex::new(None),
+        error: Mutex::new(None),
+    });
+
+    let shared_entries = Arc::clone(&entries);
+    let shared_progress = Arc::clone(&progress);
+    thread::spawn(move || {
+        let _child = child; // keep journalctl running for the life of the stream
+        let mut reader = BufReader::new(stdout);
+        let mut chunk = Vec::with_capacity(CHUNK_SIZE);
+        let mut record = String::new();
+        loop {
+            record.clear();
+            // `json-seq` frames each record with a leading RS (0x1e) and a
+            // trailing newline; strip both before handing the line to the
+            // regular per-format line parser.
+            if reader.read_line(&mut record).unwrap_or(0) == 0 {
+                break;
+            }
+            shared_progress.lines_parsed.fetch_add(1, Ordering::Relaxed);
+            let line = record
+                .trim_start_matches('\u{1e}')
+                .trim_end_matches(['\r', '\n']);
+            if let Ok(entry) = LogFormat::Journald.parse(line) {
+                chunk.push(entry);
+            }
+            if chunk.len() >= CHUNK_SIZE {
+                shared_entries.lock().unwrap().append(&mut chunk);
+            }
+        }
+        if !chunk.is_empty() {
+            shared_entries.lock().unwrap().append(&mut chunk);
+        }
+        shared_progress.done.store(true, Ordering::Relaxed);
+    });
+
+    Ok(Loader { entries, progress })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A missing file used to only be caught for `LogFormat::Auto`, which
+    /// samples the file to detect its format; a non-`Auto` format skipped
+    /// that sampling entirely and so never opened the file at all (see
+    /// synth-572). Both paths should now fail the same way.
+    #[test]
+    fn peek_format_errors_on_a_missing_file_for_auto() {
+        let path = Path::new("/nonexistent/does-not-exist.log");
+        assert!(peek_format(path, LogFormat::Auto).is_err());
+    }
+
+    #[test]
+    fn peek_format_errors_on_a_missing_file_for_a_concrete_format() {
+        let path = Path::new("/nonexistent/does-not-exist.log");
+        assert!(peek_format(path, LogFormat::Threadtime).is_err());
+    }
+
+    #[test]
+    fn peek_format_resolves_a_concrete_format_without_sampling() {
+        let path = std::env::temp_dir().join(format!(
+            "logcatui-test-peek-format-{}-{:?}.log",
+            std::process::id(),
+            thread::current().id()
+        ));
+        std::fs::write(&path, "not valid threadtime or time output\n").unwrap();
+
+        let resolved = peek_format(&path, LogFormat::Threadtime).unwrap();
+
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(resolved, LogFormat::Threadtime);
+    }
+}
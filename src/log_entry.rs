@@ -0,0 +1,203 @@
+use std::fmt;
+use std::str::FromStr;
+
+use chrono::NaiveDateTime;
+
+use crate::format::LogFormat;
+
+/// Android logcat severity levels, from least to most severe. Declaration
+/// order is significant: it's what `PartialOrd`/`Ord` compare by, for the
+/// minimum-level filter.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum LogLevel {
+    Verbose,
+    Debug,
+    Info,
+    Warn,
+    Error,
+    /// `F`/`A` (Fatal/Assert) — the most severe level, above `Error`.
+    Fatal,
+}
+
+impl LogLevel {
+    pub fn as_char(self) -> char {
+        match self {
+            LogLevel::Verbose => 'V',
+            LogLevel::Debug => 'D',
+            LogLevel::Info => 'I',
+            LogLevel::Warn => 'W',
+            LogLevel::Error => 'E',
+            LogLevel::Fatal => 'F',
+        }
+    }
+
+    /// Full level name, spelled out; used where `as_char`'s single letter is
+    /// too terse, e.g. the `Space`/`o` detail popup.
+    pub fn name(self) -> &'static str {
+        match self {
+            LogLevel::Verbose => "Verbose",
+            LogLevel::Debug => "Debug",
+            LogLevel::Info => "Info",
+            LogLevel::Warn => "Warn",
+            LogLevel::Error => "Error",
+            LogLevel::Fatal => "Fatal",
+        }
+    }
+}
+
+impl fmt::Display for LogLevel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_char())
+    }
+}
+
+impl FromStr for LogLevel {
+    type Err = ();
+
+    /// Accepts exactly a single-letter code (`"E"`) or a full level name
+    /// (`"Error"`, case-insensitively) — nothing shorter or longer, so a
+    /// misaligned parse doesn't silently pick up something like `"Error!"`
+    /// or a tag that happens to start with a level letter.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "V" => Ok(LogLevel::Verbose),
+            "D" => Ok(LogLevel::Debug),
+            "I" => Ok(LogLevel::Info),
+            "W" => Ok(LogLevel::Warn),
+            "E" => Ok(LogLevel::Error),
+            "F" | "A" => Ok(LogLevel::Fatal),
+            _ if s.eq_ignore_ascii_case("verbose") => Ok(LogLevel::Verbose),
+            _ if s.eq_ignore_ascii_case("debug") => Ok(LogLevel::Debug),
+            _ if s.eq_ignore_ascii_case("info") => Ok(LogLevel::Info),
+            _ if s.eq_ignore_ascii_case("warn") || s.eq_ignore_ascii_case("warning") => {
+                Ok(LogLevel::Warn)
+            }
+            _ if s.eq_ignore_ascii_case("error") => Ok(LogLevel::Error),
+            _ if s.eq_ignore_ascii_case("fatal") || s.eq_ignore_ascii_case("assert") => {
+                Ok(LogLevel::Fatal)
+            }
+            _ => Err(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod log_level_tests {
+    use super::*;
+
+    #[test]
+    fn accepts_single_letter_codes() {
+        assert_eq!("E".parse(), Ok(LogLevel::Error));
+        assert_eq!("V".parse(), Ok(LogLevel::Verbose));
+    }
+
+    #[test]
+    fn accepts_full_level_names_case_insensitively() {
+        assert_eq!("Error".parse(), Ok(LogLevel::Error));
+        assert_eq!("error".parse(), Ok(LogLevel::Error));
+        assert_eq!("Warning".parse(), Ok(LogLevel::Warn));
+    }
+
+    #[test]
+    fn rejects_trailing_garbage_after_a_valid_token() {
+        assert_eq!("Error!".parse::<LogLevel>(), Err(()));
+        assert_eq!("E!".parse::<LogLevel>(), Err(()));
+    }
+
+    #[test]
+    fn rejects_unrelated_words() {
+        assert_eq!("Info!".parse::<LogLevel>(), Err(()));
+        assert_eq!("Banana".parse::<LogLevel>(), Err(()));
+    }
+
+    #[test]
+    fn accepts_fatal_as_f_or_a_or_full_name() {
+        assert_eq!("F".parse(), Ok(LogLevel::Fatal));
+        assert_eq!("A".parse(), Ok(LogLevel::Fatal));
+        assert_eq!("Fatal".parse(), Ok(LogLevel::Fatal));
+        assert_eq!("assert".parse(), Ok(LogLevel::Fatal));
+    }
+
+    #[test]
+    fn fatal_displays_as_f() {
+        assert_eq!(LogLevel::Fatal.to_string(), "F");
+    }
+
+    #[test]
+    fn fatal_is_the_most_severe_level() {
+        assert!(LogLevel::Fatal > LogLevel::Error);
+        assert!(LogLevel::Fatal > LogLevel::Verbose);
+    }
+
+    #[test]
+    fn severity_increases_from_verbose_to_error() {
+        assert!(LogLevel::Error > LogLevel::Warn);
+        assert!(LogLevel::Warn > LogLevel::Info);
+        assert!(LogLevel::Info > LogLevel::Debug);
+        assert!(LogLevel::Debug > LogLevel::Verbose);
+    }
+}
+
+/// A single parsed line from a logcat "threadtime" dump:
+/// `MM-DD HH:MM:SS.mmm PID TID LEVEL TAG: message`
+#[derive(Debug, Clone)]
+pub struct LogEntry {
+    pub timestamp: NaiveDateTime,
+    pub pid: u32,
+    pub tid: u32,
+    pub level: LogLevel,
+    pub tag: String,
+    pub message: String,
+    /// The unparsed line this entry came from, kept around for the `Alt+R`
+    /// raw view (see `App::show_raw`) since it can carry information the
+    /// parsed columns don't, e.g. a layout `LogFormat` doesn't fully model.
+    pub raw: String,
+}
+
+impl fmt::Display for LogEntry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} {} {} {} {}: {}",
+            self.timestamp.format("%m-%d %H:%M:%S%.f"),
+            self.pid,
+            self.tid,
+            self.level,
+            self.tag,
+            self.message
+        )
+    }
+}
+
+impl FromStr for LogEntry {
+    type Err = ();
+
+    /// Parses assuming the `threadtime` format; use
+    /// [`LogFormat::parse`] to pick a different one.
+    fn from_str(line: &str) -> Result<Self, Self::Err> {
+        LogFormat::Threadtime.parse(line)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_millisecond_timestamp() {
+        let entry: LogEntry = "08-08 10:00:00.123 100 200 I Tag: msg".parse().unwrap();
+        assert_eq!(entry.timestamp.format("%.f").to_string(), ".123");
+    }
+
+    #[test]
+    fn parses_microsecond_timestamp() {
+        let entry: LogEntry = "08-08 10:00:00.123456 100 200 I Tag: msg".parse().unwrap();
+        assert_eq!(entry.timestamp.format("%.f").to_string(), ".123456");
+    }
+
+    #[test]
+    fn parses_timestamp_without_fraction() {
+        let entry: LogEntry = "08-08 10:00:00 100 200 I Tag: msg".parse().unwrap();
+        assert_eq!(entry.timestamp.format("%.f").to_string(), "");
+    }
+}
@@ -0,0 +1,456 @@
+use std::io;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use anyhow::{bail, Result};
+use clap::Parser;
+use crossterm::cursor::Show;
+use crossterm::event::{
+    self, DisableBracketedPaste, DisableMouseCapture, EnableBracketedPaste, EnableMouseCapture,
+    Event, KeyCode, KeyModifiers,
+};
+use crossterm::execute;
+use crossterm::terminal::{
+    disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen,
+};
+use ratatui::backend::CrosstermBackend;
+use ratatui::Terminal;
+
+use logcatui::app::{load_logfile, App, Mode};
+use logcatui::config::{
+    load_column_widths, load_double_click_ms, load_theme, load_theme_overrides, ColumnWidth,
+    ColumnWidthConfig, TzOption,
+};
+use logcatui::format::LogFormat;
+use logcatui::state::State;
+use logcatui::styles::{self, ThemeName, ALL_THEMES};
+use logcatui::tabs::Tabs;
+use logcatui::{search, ui};
+
+/// A terminal UI for browsing and filtering Android logcat dumps.
+#[derive(Parser, Debug)]
+#[command(author, version, about)]
+struct Args {
+    /// Path(s) to one or more logcat dump files. Required unless
+    /// `--journald` is given. Passing more than one opens each in its own
+    /// tab, switched between with `Ctrl+Tab`/`Alt+1`..`Alt+9`; `--no-tui`/
+    /// `--grep` only support a single path.
+    #[arg(required_unless_present = "journald", num_args = 1..)]
+    paths: Vec<PathBuf>,
+
+    /// Alternative to opening multiple `paths` as tabs: concatenate all of
+    /// them and sort the combined entries by timestamp into a single view,
+    /// for interleaving logs from two processes captured separately. Ties
+    /// keep the order `paths` were given in. Ignored with a single path.
+    #[arg(long, conflicts_with = "journald")]
+    merge: bool,
+
+    /// Which `logcat -v` layout to parse lines as. Defaults to auto-detecting
+    /// from the first lines of the file.
+    #[arg(long, value_enum, default_value = "auto")]
+    format: LogFormat,
+
+    /// Skip the TUI and print matching lines to stdout instead, like grep.
+    #[arg(long)]
+    no_tui: bool,
+
+    /// Use as an interactive picker: `Ctrl-P` exits printing the selected
+    /// line to stdout (exit code 0); `q` exits printing nothing (exit code
+    /// 1), e.g. `grep "$(logcatui --print-on-exit file.log)" other.log`.
+    #[arg(long)]
+    print_on_exit: bool,
+
+    /// Pattern to search for. Required with --no-tui.
+    #[arg(long)]
+    search: Option<String>,
+
+    /// Shorthand for `--no-tui --search PATTERN`, for piping matches
+    /// straight into another command: `logcatui file.log --grep ANR | ...`.
+    #[arg(long, conflicts_with_all = ["no_tui", "search"])]
+    grep: Option<String>,
+
+    /// Include N entries of context after each match (like grep -A).
+    #[arg(long, default_value_t = 0, requires = "no_tui")]
+    after_context: usize,
+
+    /// Include N entries of context before each match (like grep -B).
+    #[arg(long, default_value_t = 0, requires = "no_tui")]
+    before_context: usize,
+
+    /// Override the Tag column width: a number of characters, or `auto` to
+    /// size it to the widest tag loaded. Defaults to 18, or the `[columns]`
+    /// table of `~/.config/logcatui/config.toml` if set there.
+    #[arg(long)]
+    max_tag_width: Option<ColumnWidth>,
+
+    /// Override the PID column width; see `--max-tag-width`.
+    #[arg(long)]
+    max_pid_width: Option<ColumnWidth>,
+
+    /// Override the Time column width; see `--max-tag-width`.
+    #[arg(long)]
+    max_timestamp_width: Option<ColumnWidth>,
+
+    /// Minimum gap, in milliseconds, for the `Alt+T` delta column to
+    /// highlight a row's value as a notable stall.
+    #[arg(long, default_value_t = 1000)]
+    delta_threshold_ms: u64,
+
+    /// How to display timestamps: `utc` (default, shown as stored), `local`
+    /// (the system's current timezone), or a fixed offset like `+05:30`.
+    #[arg(long)]
+    tz: Option<TzOption>,
+
+    /// Pre-set the PID filter, as if `p` had been pressed on a row with
+    /// this PID.
+    #[arg(long)]
+    pid: Option<u32>,
+
+    /// Pre-set the TID filter; see `--pid`.
+    #[arg(long)]
+    tid: Option<u32>,
+
+    /// Color scheme to render with. Defaults to `default`, or `theme` in
+    /// `~/.config/logcatui/config.toml` if set there.
+    #[arg(long, value_enum)]
+    theme: Option<ThemeName>,
+
+    /// Print the names of the built-in themes and exit.
+    #[arg(long)]
+    list_themes: bool,
+
+    /// Activate a named filter preset from `~/.config/logcatui/filters.toml`
+    /// at startup, as if it had been picked from the `F` popup.
+    #[arg(long)]
+    preset: Option<String>,
+
+    /// Stream live from `journalctl --follow` instead of reading a file,
+    /// optionally restricted to a single systemd unit (`--journald=sshd`).
+    /// Requires `journalctl` in PATH. Conflicts with `path`.
+    #[arg(long, num_args = 0..=1, value_name = "UNIT", conflicts_with = "paths")]
+    journald: Option<Option<String>>,
+
+    /// Developer/contributor diagnostics mode: starts with the debug status
+    /// bar segment already on, and reports how many lines failed to parse
+    /// once loading finishes.
+    #[arg(long)]
+    trace: bool,
+
+    /// Show the debug status bar segment (FPS/timing) from startup, same as
+    /// pressing `Alt+D` once the TUI is up. See `--trace` for the fuller
+    /// diagnostics mode.
+    #[arg(short = 'd', long)]
+    debug: bool,
+
+    /// Remember the scroll position, filters, and bookmarks for each file
+    /// between runs, in `~/.local/share/logcatui/session_state.json`.
+    /// Restored on the next run only if the file hasn't changed size or
+    /// modification time since. Has no effect with `--merge`/`--journald`,
+    /// which have no single on-disk file to key the state by.
+    #[arg(long)]
+    persist_session: bool,
+
+    /// Render inline instead of switching to the terminal's alternate
+    /// screen, so the final frame stays behind in scrollback after exit
+    /// instead of vanishing when the alternate screen is left.
+    #[arg(long)]
+    no_alt_screen: bool,
+}
+
+impl Args {
+    fn column_width_config(&self) -> ColumnWidthConfig {
+        ColumnWidthConfig {
+            max_tag_width: self.max_tag_width,
+            max_pid_width: self.max_pid_width,
+            max_timestamp_width: self.max_timestamp_width,
+        }
+        .or(load_column_widths())
+    }
+}
+
+/// Prints a one-line `Error: ...` message (chaining causes with `: `, no
+/// backtrace) and exits non-zero on failure, rather than the `Debug`-styled
+/// dump the default `fn main() -> Result<()>` termination prints.
+fn main() {
+    if let Err(err) = try_main() {
+        eprintln!("Error: {err:#}");
+        std::process::exit(1);
+    }
+}
+
+fn try_main() -> Result<()> {
+    let mut args = Args::parse();
+    if let Some(pattern) = args.grep.take() {
+        args.no_tui = true;
+        args.search = Some(pattern);
+    }
+
+    if args.list_themes {
+        for theme in ALL_THEMES {
+            println!("{}", theme.name());
+        }
+        return Ok(());
+    }
+
+    styles::init_theme(
+        args.theme
+            .or_else(load_theme)
+            .unwrap_or_default()
+            .config()
+            .with_overrides(load_theme_overrides()),
+    );
+
+    if args.no_tui {
+        return run_search_export(&args);
+    }
+
+    let column_widths = args.column_width_config();
+    let tz = args.tz.unwrap_or(TzOption::Utc);
+    let apps = match &args.journald {
+        Some(unit) => vec![App::new_journald(
+            unit.clone(),
+            column_widths,
+            load_double_click_ms(),
+            args.delta_threshold_ms,
+            tz,
+            args.pid,
+            args.tid,
+            args.preset.clone(),
+            args.trace,
+        )?],
+        None if args.merge && args.paths.len() > 1 => vec![App::new_merged(
+            args.paths.clone(),
+            args.format,
+            column_widths,
+            load_double_click_ms(),
+            args.delta_threshold_ms,
+            tz,
+            args.pid,
+            args.tid,
+            args.preset.clone(),
+            args.trace,
+        )?],
+        None => args
+            .paths
+            .iter()
+            .map(|path| {
+                App::new(
+                    path.clone(),
+                    args.format,
+                    column_widths,
+                    load_double_click_ms(),
+                    args.delta_threshold_ms,
+                    tz,
+                    args.pid,
+                    args.tid,
+                    args.preset.clone(),
+                    args.trace,
+                    args.persist_session,
+                )
+            })
+            .collect::<Result<Vec<_>>>()?,
+    };
+    let mut tabs = Tabs::new(apps);
+    for app in tabs.iter_mut() {
+        app.debug |= args.debug;
+    }
+
+    install_panic_hook(args.no_alt_screen);
+
+    enable_raw_mode()?;
+    let mut stdout = io::stdout();
+    if !args.no_alt_screen {
+        execute!(stdout, EnterAlternateScreen)?;
+    }
+    execute!(stdout, EnableMouseCapture, EnableBracketedPaste)?;
+    let backend = CrosstermBackend::new(stdout);
+    let mut terminal = Terminal::new(backend)?;
+
+    let result = run(&mut terminal, &mut tabs);
+
+    for app in tabs.iter_mut() {
+        app.save_session();
+    }
+
+    disable_raw_mode()?;
+    execute!(terminal.backend_mut(), DisableBracketedPaste)?;
+    if !args.no_alt_screen {
+        execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
+    }
+    execute!(terminal.backend_mut(), DisableMouseCapture)?;
+    terminal.show_cursor()?;
+
+    result?;
+
+    if args.print_on_exit {
+        match tabs.iter_mut().find_map(|app| app.picked.take()) {
+            Some(line) => {
+                println!("{line}");
+                std::process::exit(0);
+            }
+            None => std::process::exit(1),
+        }
+    }
+
+    Ok(())
+}
+
+/// `--no-tui --search PATTERN`: print matching (and, with `--after-context`
+/// / `--before-context`, surrounding) entries and exit, the way `grep -A/-B`
+/// would, but parsed as logcat entries.
+fn run_search_export(args: &Args) -> Result<()> {
+    let path = match args.paths.as_slice() {
+        [path] => path,
+        [] => bail!("--no-tui requires a path; --journald is not supported with --no-tui"),
+        _ => bail!("--no-tui only supports a single path"),
+    };
+    let (entries, _, _) = load_logfile(path, args.format)?;
+
+    let mut state = State::new();
+    state.quick_search = args.search.clone();
+    state.update(&entries);
+
+    let indices = search::expand_context(
+        &state.results,
+        args.before_context,
+        args.after_context,
+        entries.len(),
+    );
+
+    let mut previous: Option<usize> = None;
+    for index in indices {
+        if let Some(previous) = previous {
+            if index > previous + 1 {
+                println!("--");
+            }
+        }
+        println!("{}", entries[index]);
+        previous = Some(index);
+    }
+
+    Ok(())
+}
+
+/// How long to block waiting for the next terminal event. Bounded rather
+/// than infinite so background work drained in [`App::tick`] (the
+/// incremental loader, search workers, `--journald` streaming) can still
+/// show up on screen without a key press.
+const EVENT_POLL_TIMEOUT: Duration = Duration::from_millis(250);
+
+/// Make sure a panic mid-session doesn't leave the user's shell stuck in
+/// raw mode with the cursor hidden and (unless `--no-alt-screen`) the
+/// alternate screen still up hiding their scrollback — restore the
+/// terminal first, then hand off to whatever hook was already installed
+/// (the default one prints the panic message).
+fn install_panic_hook(no_alt_screen: bool) {
+    let previous_hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        let _ = disable_raw_mode();
+        let mut stdout = io::stdout();
+        if !no_alt_screen {
+            let _ = execute!(stdout, LeaveAlternateScreen);
+        }
+        let _ = execute!(stdout, DisableMouseCapture, DisableBracketedPaste, Show);
+        previous_hook(info);
+    }));
+}
+
+/// Suspend the process to the background on `Ctrl+Z`, the way any other
+/// terminal-raw-mode program does: leave the alternate screen and raw mode
+/// first so the shell prompt looks normal while stopped, then actually stop
+/// via `SIGSTOP`. When the shell resumes us with `fg`, execution continues
+/// right here, so the caller just needs to restore the TUI and redraw.
+#[cfg(unix)]
+fn suspend<B: ratatui::backend::Backend + io::Write>(terminal: &mut Terminal<B>) -> Result<()> {
+    disable_raw_mode()?;
+    execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
+
+    nix::sys::signal::raise(nix::sys::signal::Signal::SIGSTOP)?;
+
+    execute!(terminal.backend_mut(), EnterAlternateScreen)?;
+    enable_raw_mode()?;
+    Ok(())
+}
+
+/// The event loop. Blocks in [`event::poll`] for up to [`EVENT_POLL_TIMEOUT`]
+/// between iterations rather than polling with a zero timeout, and only
+/// redraws when [`App::dirty`] is actually set (by a key/mouse/paste/resize
+/// event or a background loader/search-worker update drained in
+/// [`App::tick`]), so an idle session sits mostly parked in the blocking
+/// poll instead of pegging a CPU core; [`App::fps`] is computed from
+/// [`App::record_frame`], which only increments on those real redraws.
+fn run<B: ratatui::backend::Backend + io::Write>(
+    terminal: &mut Terminal<B>,
+    tabs: &mut Tabs,
+) -> Result<()> {
+    loop {
+        if tabs.active().dirty {
+            let titles: Vec<String> = tabs
+                .paths()
+                .map(|path| path.display().to_string())
+                .collect();
+            let active_index = tabs.active_index();
+            let app = tabs.active_mut();
+            terminal.draw(|frame| ui::draw(frame, app, &titles, active_index))?;
+            app.record_frame();
+            app.dirty = false;
+        }
+
+        if event::poll(EVENT_POLL_TIMEOUT)? {
+            match event::read()? {
+                #[cfg(unix)]
+                Event::Key(key)
+                    if key.code == KeyCode::Char('z')
+                        && key.modifiers.contains(KeyModifiers::CONTROL) =>
+                {
+                    suspend(terminal)?;
+                    tabs.active_mut().dirty = true;
+                }
+                Event::Key(key)
+                    if tabs.active().mode == Mode::Normal
+                        && key.code == KeyCode::Tab
+                        && key.modifiers.contains(KeyModifiers::CONTROL) =>
+                {
+                    tabs.activate_next();
+                }
+                Event::Key(key)
+                    if tabs.active().mode == Mode::Normal
+                        && key.modifiers.contains(KeyModifiers::ALT) =>
+                {
+                    if let KeyCode::Char(digit @ '1'..='9') = key.code {
+                        tabs.select(digit as usize - '1' as usize);
+                    }
+                }
+                Event::Key(key) => {
+                    let app = tabs.active_mut();
+                    match app.mode {
+                        Mode::Normal => app.regular_input(key)?,
+                        Mode::Command => app.command_input(key),
+                        Mode::TagFilter => app.tag_filter_input(key),
+                        Mode::FilterExpr => app.filter_expr_input(key),
+                        Mode::QuickSearch => app.quick_search_input(key),
+                        Mode::PresetPicker => app.preset_picker_input(key),
+                        Mode::QuickFilter => app.quick_filter_input(key),
+                        Mode::TagStats => app.tag_stats_input(key),
+                        Mode::Histogram => app.histogram_input(key),
+                        Mode::PinnedHighlights => app.pinned_highlights_input(key),
+                        Mode::Bookmarks => app.bookmarks_input(key),
+                        Mode::EntryDetail => app.entry_detail_input(key),
+                    }
+                }
+                Event::Mouse(mouse) => tabs.active_mut().handle_mouse(mouse),
+                Event::Paste(text) => tabs.active_mut().handle_paste(&text),
+                Event::Resize(_, _) => tabs.active_mut().resize(),
+                _ => {}
+            }
+        }
+
+        for app in tabs.iter_mut() {
+            app.tick();
+        }
+
+        if tabs.active().should_quit {
+            return Ok(());
+        }
+    }
+}
@@ -0,0 +1,377 @@
+//! Quick-search matching and context expansion for `--search` exports. This
+//! is the one and only search module in the crate — there's no `search/`
+//! directory or parallel `search::state`/`search::quick` hierarchy to drift
+//! out of sync with it.
+
+use crate::fuzzy;
+use crate::log_entry::LogEntry;
+
+/// Which column a scoped quick-search pattern restricts itself to; see
+/// [`parse_pattern`]. `Any` is the unscoped, pre-existing behavior: tag or
+/// message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SearchScope {
+    Any,
+    Tag,
+    Message,
+    Pid,
+    Tid,
+}
+
+/// A quick-search pattern split into its [`SearchScope`] and the text to
+/// match, as parsed by [`parse_pattern`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScopedPattern {
+    pub scope: SearchScope,
+    pub text: String,
+}
+
+/// Parse a `/` quick-search pattern, recognizing the `tag:`, `msg:`,
+/// `pid:`, and `tid:` column-scope prefixes (e.g. `tag:Camera`,
+/// `pid:1234`), so a search for a bare number doesn't also match every
+/// timestamp and message containing it. An unrecognized prefix, or none at
+/// all, falls back to [`SearchScope::Any`] with the whole pattern as
+/// literal text — so `1234` still searches tag+message like before, and
+/// `nonsense:1234` is treated as the literal text `nonsense:1234` rather
+/// than an error.
+pub fn parse_pattern(pattern: &str) -> ScopedPattern {
+    const PREFIXES: [(&str, SearchScope); 4] = [
+        ("tag:", SearchScope::Tag),
+        ("msg:", SearchScope::Message),
+        ("pid:", SearchScope::Pid),
+        ("tid:", SearchScope::Tid),
+    ];
+    for (prefix, scope) in PREFIXES {
+        if let Some(text) = pattern.strip_prefix(prefix) {
+            return ScopedPattern {
+                scope,
+                text: text.to_lowercase(),
+            };
+        }
+    }
+    ScopedPattern {
+        scope: SearchScope::Any,
+        text: pattern.to_lowercase(),
+    }
+}
+
+/// Case-insensitive substring match against the column(s) `pattern`'s scope
+/// selects; see [`parse_pattern`]. Unscoped, this is tag-or-message, like
+/// [`crate::filter::TagFilter`]'s tag matching: lowercase both sides rather
+/// than relying on the pattern already being lowercase.
+pub fn matches(entry: &LogEntry, pattern: &str) -> bool {
+    let ScopedPattern { scope, text } = parse_pattern(pattern);
+    match scope {
+        SearchScope::Any => {
+            entry.message.to_lowercase().contains(&text) || entry.tag.to_lowercase().contains(&text)
+        }
+        SearchScope::Tag => entry.tag.to_lowercase().contains(&text),
+        SearchScope::Message => entry.message.to_lowercase().contains(&text),
+        SearchScope::Pid => entry.pid.to_string().contains(&text),
+        SearchScope::Tid => entry.tid.to_string().contains(&text),
+    }
+}
+
+/// fzf-style fuzzy equivalent of [`matches`]: `pattern`'s characters must
+/// all occur in the scoped column(s), in order, but not necessarily
+/// contiguously, via [`fuzzy::subsequence_positions`]. Ranking isn't
+/// needed since the table keeps its natural (load/sort) order rather than
+/// best-match order; this is pure membership.
+pub fn fuzzy_matches(entry: &LogEntry, pattern: &str) -> bool {
+    let ScopedPattern { scope, text } = parse_pattern(pattern);
+    match scope {
+        SearchScope::Any => {
+            fuzzy::subsequence_positions(&entry.message, &text).is_some()
+                || fuzzy::subsequence_positions(&entry.tag, &text).is_some()
+        }
+        SearchScope::Tag => fuzzy::subsequence_positions(&entry.tag, &text).is_some(),
+        SearchScope::Message => fuzzy::subsequence_positions(&entry.message, &text).is_some(),
+        SearchScope::Pid => fuzzy::subsequence_positions(&entry.pid.to_string(), &text).is_some(),
+        SearchScope::Tid => fuzzy::subsequence_positions(&entry.tid.to_string(), &text).is_some(),
+    }
+}
+
+/// Fuzzy equivalent of [`match_spans`]: one byte-range span per matched
+/// character rather than one span per contiguous run, since a fuzzy match's
+/// characters are usually scattered through the message. Feeds the same
+/// `search_spans` highlighting as exact search; see
+/// [`crate::display::DisplayData::as_row`].
+pub fn fuzzy_match_spans(message: &str, pattern: &str) -> Vec<(usize, usize)> {
+    let ScopedPattern { scope, text } = parse_pattern(pattern);
+    if text.is_empty() || !matches!(scope, SearchScope::Any | SearchScope::Message) {
+        return Vec::new();
+    }
+    fuzzy::subsequence_positions(message, &text).unwrap_or_default()
+}
+
+/// Byte ranges in `message` where `pattern` occurs, case-insensitively, for
+/// highlighting a live quick search; see [`crate::display::DisplayData::as_row`]'s
+/// `search_spans` parameter. Empty if `pattern` is empty (an empty pattern
+/// would otherwise "match" at every offset) or scoped to a column other
+/// than the message (`tag:`/`pid:`/`tid:`), since there's nothing in
+/// `message` to highlight for those. The returned ranges are always valid
+/// byte offsets into `message` itself; see [`find_case_insensitive`] for why
+/// that isn't as trivial as it sounds.
+pub fn match_spans(message: &str, pattern: &str) -> Vec<(usize, usize)> {
+    let ScopedPattern { scope, text } = parse_pattern(pattern);
+    if text.is_empty() || !matches!(scope, SearchScope::Any | SearchScope::Message) {
+        return Vec::new();
+    }
+    find_case_insensitive(message, &text)
+}
+
+/// Case-insensitive, non-overlapping occurrences of `needle` (expected
+/// already lowercased) in `haystack`, as byte ranges valid in `haystack`
+/// itself. Case-folds `haystack`'s characters one at a time and compares
+/// them against `needle` directly, rather than searching inside a
+/// separately lowercased copy of `haystack` and reusing the byte offsets
+/// found there: lowercasing can change a character's UTF-8 length (e.g.
+/// `İ` is 2 bytes but lowercases to the 3-byte `i̇`), which would silently
+/// shift every offset found after it out of alignment with the original
+/// string — landing a highlight a few bytes off, or slicing it on a
+/// boundary that isn't a char boundary at all.
+fn find_case_insensitive(haystack: &str, needle: &str) -> Vec<(usize, usize)> {
+    if needle.is_empty() {
+        return Vec::new();
+    }
+    let needle_chars: Vec<char> = needle.chars().flat_map(char::to_lowercase).collect();
+    let chars: Vec<(usize, char)> = haystack.char_indices().collect();
+
+    let mut spans = Vec::new();
+    let mut start = 0;
+    'starts: while start < chars.len() {
+        let mut needle_pos = 0;
+        let mut pos = start;
+        while needle_pos < needle_chars.len() {
+            let Some(&(_, ch)) = chars.get(pos) else {
+                break 'starts;
+            };
+            for folded in ch.to_lowercase() {
+                if needle_pos >= needle_chars.len() || folded != needle_chars[needle_pos] {
+                    start += 1;
+                    continue 'starts;
+                }
+                needle_pos += 1;
+            }
+            pos += 1;
+        }
+        let match_start = chars[start].0;
+        let match_end = chars.get(pos).map_or(haystack.len(), |&(b, _)| b);
+        spans.push((match_start, match_end));
+        start = pos.max(start + 1);
+    }
+    spans
+}
+
+/// Expand each match in `matches` into `[match - before, match + after]`
+/// (clamped to `0..len`), merge overlapping/adjacent intervals, and return
+/// the full sorted, deduplicated list of entry indices to display.
+pub fn expand_context(matches: &[usize], before: usize, after: usize, len: usize) -> Vec<usize> {
+    if matches.is_empty() || len == 0 {
+        return Vec::new();
+    }
+
+    let mut intervals: Vec<(usize, usize)> = matches
+        .iter()
+        .map(|&m| {
+            let start = m.saturating_sub(before);
+            let end = (m + after).min(len - 1);
+            (start, end)
+        })
+        .collect();
+    intervals.sort_unstable();
+
+    let mut merged: Vec<(usize, usize)> = Vec::new();
+    for (start, end) in intervals.drain(..) {
+        match merged.last_mut() {
+            Some(last) if start <= last.1 + 1 => last.1 = last.1.max(end),
+            _ => merged.push((start, end)),
+        }
+    }
+
+    merged
+        .into_iter()
+        .flat_map(|(start, end)| start..=end)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::log_entry::LogLevel;
+
+    fn entry(tag: &str, message: &str) -> LogEntry {
+        LogEntry {
+            timestamp: chrono::NaiveDateTime::default(),
+            pid: 0,
+            tid: 0,
+            level: LogLevel::Info,
+            tag: tag.to_string(),
+            message: message.to_string(),
+            raw: "raw".to_string(),
+        }
+    }
+
+    #[test]
+    fn matches_message_regardless_of_case() {
+        assert!(matches(&entry("Tag", "Camera opened"), "camera"));
+        assert!(matches(&entry("Tag", "Camera opened"), "CAMERA"));
+    }
+
+    #[test]
+    fn matches_tag_regardless_of_case() {
+        assert!(matches(&entry("CameraService", "msg"), "cameraservice"));
+    }
+
+    #[test]
+    fn matches_non_ascii_text_case_insensitively() {
+        assert!(matches(&entry("Tag", "café crashed"), "CAFÉ"));
+    }
+
+    #[test]
+    fn tag_prefix_scopes_to_the_tag_column_only() {
+        assert!(matches(&entry("CameraService", "unrelated"), "tag:camera"));
+        assert!(!matches(&entry("Other", "camera opened"), "tag:camera"));
+    }
+
+    #[test]
+    fn msg_prefix_scopes_to_the_message_column_only() {
+        assert!(matches(&entry("Other", "camera opened"), "msg:camera"));
+        assert!(!matches(&entry("CameraService", "unrelated"), "msg:camera"));
+    }
+
+    #[test]
+    fn pid_prefix_scopes_to_the_pid_column() {
+        let mut e = entry("Tag", "msg");
+        e.pid = 1234;
+        assert!(matches(&e, "pid:1234"));
+        assert!(!matches(&e, "pid:9999"));
+    }
+
+    #[test]
+    fn tid_prefix_scopes_to_the_tid_column() {
+        let mut e = entry("Tag", "msg");
+        e.tid = 5678;
+        assert!(matches(&e, "tid:5678"));
+        assert!(!matches(&e, "tid:1111"));
+    }
+
+    #[test]
+    fn unknown_prefix_falls_back_to_literal_text_in_any_scope() {
+        let parsed = parse_pattern("nonsense:1234");
+        assert_eq!(parsed.scope, SearchScope::Any);
+        assert_eq!(parsed.text, "nonsense:1234");
+    }
+
+    #[test]
+    fn scoped_patterns_produce_no_message_highlight_spans() {
+        assert!(match_spans("camera opened", "tag:camera").is_empty());
+        assert!(match_spans("camera opened", "pid:1234").is_empty());
+    }
+
+    #[test]
+    fn msg_scoped_pattern_still_highlights_the_message() {
+        assert_eq!(match_spans("camera opened", "msg:camera"), vec![(0, 6)]);
+    }
+
+    #[test]
+    fn finds_every_occurrence_case_insensitively() {
+        assert_eq!(
+            match_spans("Camera opened, camera closed", "camera"),
+            vec![(0, 6), (15, 21)]
+        );
+    }
+
+    #[test]
+    fn empty_pattern_matches_nothing() {
+        assert!(match_spans("anything", "").is_empty());
+    }
+
+    #[test]
+    fn merges_overlapping_windows() {
+        let indices = expand_context(&[5, 7], 1, 1, 100);
+        assert_eq!(indices, vec![4, 5, 6, 7, 8]);
+    }
+
+    #[test]
+    fn keeps_disjoint_windows_separate() {
+        let indices = expand_context(&[1, 50], 0, 0, 100);
+        assert_eq!(indices, vec![1, 50]);
+    }
+
+    #[test]
+    fn clamps_to_bounds() {
+        let indices = expand_context(&[0], 5, 5, 3);
+        assert_eq!(indices, vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn finds_matches_after_cyrillic_text_without_panicking() {
+        let message = "привет камера мир";
+        let spans = match_spans(message, "камера");
+        assert_eq!(spans, vec![(13, 25)]);
+        assert_eq!(&message[spans[0].0..spans[0].1], "камера");
+    }
+
+    #[test]
+    fn finds_matches_after_emoji_without_panicking() {
+        let message = "🚀🚀 camera 🔥";
+        let spans = match_spans(message, "camera");
+        assert_eq!(spans, vec![(9, 15)]);
+        assert_eq!(&message[spans[0].0..spans[0].1], "camera");
+    }
+
+    #[test]
+    fn finds_matches_after_cjk_text_without_panicking() {
+        let message = "相机服务 camera 日本語";
+        let spans = match_spans(message, "camera");
+        assert_eq!(spans, vec![(13, 19)]);
+        assert_eq!(&message[spans[0].0..spans[0].1], "camera");
+    }
+
+    /// `İ` (U+0130) lowercases to the two-character, 3-byte `i̇`, one byte
+    /// longer than `İ` itself (2 bytes) — exactly the kind of length change
+    /// that makes offsets found in a lowercased copy drift out of alignment
+    /// with the original string. See [`find_case_insensitive`].
+    #[test]
+    fn finds_matches_after_a_lowercase_expanding_character() {
+        let message = "İstanbul camera test";
+        let spans = match_spans(message, "camera");
+        assert_eq!(spans, vec![(10, 16)]);
+        assert_eq!(&message[spans[0].0..spans[0].1], "camera");
+    }
+
+    #[test]
+    fn fuzzy_matches_non_contiguous_characters_in_order() {
+        assert!(fuzzy_matches(&entry("Tag", "Camera opened"), "cmrpnd"));
+        assert!(!fuzzy_matches(&entry("Tag", "Camera opened"), "dnpmrc"));
+    }
+
+    #[test]
+    fn fuzzy_matches_respects_column_scopes() {
+        assert!(fuzzy_matches(
+            &entry("CameraService", "unrelated"),
+            "tag:cmsv"
+        ));
+        assert!(!fuzzy_matches(&entry("Other", "camera opened"), "tag:cmsv"));
+    }
+
+    #[test]
+    fn fuzzy_match_spans_returns_one_span_per_matched_character() {
+        assert_eq!(
+            fuzzy_match_spans("Camera opened", "cmr"),
+            vec![(0, 1), (2, 3), (4, 5)]
+        );
+    }
+
+    #[test]
+    fn fuzzy_match_spans_empty_when_pattern_does_not_match() {
+        assert!(fuzzy_match_spans("Camera opened", "xyz").is_empty());
+    }
+
+    #[test]
+    fn matches_a_pattern_entirely_made_of_multi_byte_characters() {
+        let spans = match_spans("поиск камера поиск", "камера");
+        assert_eq!(spans, vec![(11, 23)]);
+    }
+}
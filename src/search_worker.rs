@@ -0,0 +1,89 @@
+//! Background quick-search matching for large files. [`spawn`] scans a
+//! snapshot of the filtered rows against a pattern on its own thread, the
+//! same way [`crate::loader::spawn`] parses a file off the UI thread, so a
+//! scan over a multi-million-line file doesn't block the event loop. Matches
+//! stream into a shared buffer in chunks as they're found, instead of all at
+//! once at the end, so [`crate::app::App::tick`] can make early matches
+//! navigable before the scan completes.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+use crate::log_entry::LogEntry;
+use crate::search;
+
+/// How many matches accumulate before being handed to the shared buffer, to
+/// keep lock contention with the UI thread low; see [`crate::loader::spawn`]'s
+/// `CHUNK_SIZE`.
+const CHUNK_SIZE: usize = 200;
+
+/// A quick-search scan running on a background thread. `matches` fills in as
+/// the scan progresses, in chunks of [`CHUNK_SIZE`]; the caller is expected
+/// to drain it periodically (see `App::tick`) and to call [`cancel`](Self::cancel)
+/// once the pattern changes, so a stale scan doesn't keep burning CPU after
+/// its results are no longer wanted.
+pub struct SearchWorker {
+    pub matches: Arc<Mutex<Vec<usize>>>,
+    cancelled: Arc<AtomicBool>,
+    done: Arc<AtomicBool>,
+}
+
+impl SearchWorker {
+    pub fn is_done(&self) -> bool {
+        self.done.load(Ordering::Relaxed)
+    }
+
+    /// Ask the background thread to stop before its next candidate, without
+    /// waiting for it to actually exit; dropping `self` is enough for the
+    /// thread to run to completion harmlessly if it doesn't notice in time.
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+}
+
+/// Start matching `pattern` against `candidates` (source-entry index plus a
+/// clone of the entry itself, in display order) on a background thread.
+/// Cloning the candidate entries up front, rather than sharing `entries`
+/// itself, means the worker never needs to borrow `App` across frames — see
+/// [`crate::app::App::run_incremental_search`]. `fuzzy` selects
+/// [`search::fuzzy_matches`] over the default [`search::matches`]; see
+/// [`crate::state::State::fuzzy`].
+pub fn spawn(candidates: Vec<(usize, LogEntry)>, pattern: String, fuzzy: bool) -> SearchWorker {
+    let matches = Arc::new(Mutex::new(Vec::new()));
+    let cancelled = Arc::new(AtomicBool::new(false));
+    let done = Arc::new(AtomicBool::new(false));
+
+    let shared_matches = Arc::clone(&matches);
+    let shared_cancelled = Arc::clone(&cancelled);
+    let shared_done = Arc::clone(&done);
+    thread::spawn(move || {
+        let mut chunk = Vec::with_capacity(CHUNK_SIZE);
+        for (index, entry) in candidates {
+            if shared_cancelled.load(Ordering::Relaxed) {
+                return;
+            }
+            let is_match = if fuzzy {
+                search::fuzzy_matches(&entry, &pattern)
+            } else {
+                search::matches(&entry, &pattern)
+            };
+            if is_match {
+                chunk.push(index);
+            }
+            if chunk.len() >= CHUNK_SIZE {
+                shared_matches.lock().unwrap().append(&mut chunk);
+            }
+        }
+        if !chunk.is_empty() {
+            shared_matches.lock().unwrap().append(&mut chunk);
+        }
+        shared_done.store(true, Ordering::Relaxed);
+    });
+
+    SearchWorker {
+        matches,
+        cancelled,
+        done,
+    }
+}
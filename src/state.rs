@@ -0,0 +1,257 @@
+//! Data-level model shared by the TUI and the `--no-tui` export path: which
+//! rows pass the active [`Filter`] and which of those also match the quick
+//! search pattern.
+
+use std::cmp::Ordering;
+use std::fmt;
+
+use crate::filter::{self, Filter};
+use crate::log_entry::LogEntry;
+use crate::search;
+
+/// Which column [`State::filtered_indices`] is ordered by, when a sort is
+/// active; see [`App::cycle_sort_column`].
+///
+/// [`App::cycle_sort_column`]: crate::app::App::cycle_sort_column
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SortColumn {
+    Timestamp,
+    Pid,
+    Tid,
+    Level,
+    Tag,
+}
+
+impl SortColumn {
+    fn compare(self, a: &LogEntry, b: &LogEntry) -> Ordering {
+        match self {
+            SortColumn::Timestamp => a.timestamp.cmp(&b.timestamp),
+            SortColumn::Pid => a.pid.cmp(&b.pid),
+            SortColumn::Tid => a.tid.cmp(&b.tid),
+            SortColumn::Level => a.level.cmp(&b.level),
+            SortColumn::Tag => a.tag.cmp(&b.tag),
+        }
+    }
+}
+
+impl fmt::Display for SortColumn {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let label = match self {
+            SortColumn::Timestamp => "Time",
+            SortColumn::Pid => "PID",
+            SortColumn::Tid => "TID",
+            SortColumn::Level => "Level",
+            SortColumn::Tag => "Tag",
+        };
+        write!(f, "{label}")
+    }
+}
+
+/// Which way [`SortColumn`] orders rows; see [`App::toggle_sort_direction`].
+///
+/// [`App::toggle_sort_direction`]: crate::app::App::toggle_sort_direction
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum SortDirection {
+    #[default]
+    Ascending,
+    Descending,
+}
+
+impl fmt::Display for SortDirection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let label = match self {
+            SortDirection::Ascending => "ascending",
+            SortDirection::Descending => "descending",
+        };
+        write!(f, "{label}")
+    }
+}
+
+/// How many entries [`State::record_search`] keeps before dropping the
+/// oldest.
+pub const MAX_SEARCH_HISTORY: usize = 100;
+
+#[derive(Debug, Default)]
+pub struct State {
+    pub filter: Filter,
+    pub quick_search: Option<String>,
+    /// fzf-style fuzzy quick search (`Ctrl-F` in [`Mode::QuickSearch`]):
+    /// `quick_search`'s characters must appear in order but not necessarily
+    /// contiguously, via [`search::fuzzy_matches`], instead of requiring an
+    /// exact substring. Off by default since fuzzy matching is slower on a
+    /// large file; see [`App::run_incremental_search`].
+    ///
+    /// [`Mode::QuickSearch`]: crate::app::Mode::QuickSearch
+    /// [`App::run_incremental_search`]: crate::app::App::run_incremental_search
+    pub fuzzy: bool,
+    /// Column the table is sorted by, or `None` for load order.
+    pub sort_column: Option<SortColumn>,
+    pub sort_direction: SortDirection,
+    pub filtered_indices: Vec<usize>,
+    pub results: Vec<usize>,
+    /// Committed `/` searches, most recent first, deduplicated; see
+    /// [`State::record_search`]. Loaded from and persisted to
+    /// `~/.local/share/logcatui/search_history` by
+    /// [`crate::config::load_search_history`]/[`crate::config::save_search_history`].
+    pub search_history: Vec<String>,
+}
+
+impl State {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record `pattern` as the most recently committed `/` search: move it
+    /// to the front if already present, keeping the history deduplicated,
+    /// then drop the oldest entries past [`MAX_SEARCH_HISTORY`].
+    pub fn record_search(&mut self, pattern: String) {
+        self.search_history.retain(|existing| existing != &pattern);
+        self.search_history.insert(0, pattern);
+        self.search_history.truncate(MAX_SEARCH_HISTORY);
+    }
+
+    /// Recompute `filtered_indices` and `results` against `entries`.
+    pub fn update(&mut self, entries: &[LogEntry]) {
+        self.refresh_filter(entries);
+        self.results = Self::matching_results(
+            entries,
+            &self.filtered_indices,
+            &self.quick_search,
+            self.fuzzy,
+        );
+    }
+
+    /// Recompute `filtered_indices` only, leaving `results` untouched. Split
+    /// out of [`State::update`] for [`crate::app::App::run_incremental_search`],
+    /// which runs the (potentially slow, on a huge file) search-matching step
+    /// on a background thread instead of inline; see
+    /// [`crate::search_worker`].
+    pub fn refresh_filter(&mut self, entries: &[LogEntry]) {
+        self.filtered_indices = filter::apply(entries, &self.filter);
+
+        if let Some(column) = self.sort_column {
+            // `sort_by` (not `sort_unstable_by`) so entries that compare
+            // equal on `column` keep their relative load order.
+            self.filtered_indices.sort_by(|&a, &b| {
+                let ordering = column.compare(&entries[a], &entries[b]);
+                match self.sort_direction {
+                    SortDirection::Ascending => ordering,
+                    SortDirection::Descending => ordering.reverse(),
+                }
+            });
+        }
+    }
+
+    /// `filtered_indices` that also match `quick_search`, in the same order;
+    /// the synchronous equivalent of what [`crate::search_worker::spawn`]
+    /// computes incrementally on a background thread.
+    fn matching_results(
+        entries: &[LogEntry],
+        filtered_indices: &[usize],
+        quick_search: &Option<String>,
+        fuzzy: bool,
+    ) -> Vec<usize> {
+        match quick_search {
+            Some(pattern) => filtered_indices
+                .iter()
+                .copied()
+                .filter(|&index| {
+                    if fuzzy {
+                        search::fuzzy_matches(&entries[index], pattern)
+                    } else {
+                        search::matches(&entries[index], pattern)
+                    }
+                })
+                .collect(),
+            None => Vec::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::log_entry::LogLevel;
+
+    fn entry_with_pid_tag(pid: u32, tag: &str) -> LogEntry {
+        LogEntry {
+            timestamp: chrono::NaiveDateTime::default(),
+            pid,
+            tid: 0,
+            level: LogLevel::Info,
+            tag: tag.to_string(),
+            message: "msg".to_string(),
+            raw: "raw".to_string(),
+        }
+    }
+
+    #[test]
+    fn no_sort_column_keeps_load_order() {
+        let entries = vec![entry_with_pid_tag(2, "B"), entry_with_pid_tag(1, "A")];
+        let mut state = State::new();
+        state.update(&entries);
+        assert_eq!(state.filtered_indices, vec![0, 1]);
+    }
+
+    #[test]
+    fn sorts_ascending_by_chosen_column() {
+        let entries = vec![
+            entry_with_pid_tag(2, "B"),
+            entry_with_pid_tag(1, "A"),
+            entry_with_pid_tag(3, "C"),
+        ];
+        let mut state = State::new();
+        state.sort_column = Some(SortColumn::Pid);
+        state.update(&entries);
+        assert_eq!(state.filtered_indices, vec![1, 0, 2]);
+    }
+
+    #[test]
+    fn descending_direction_reverses_the_order() {
+        let entries = vec![
+            entry_with_pid_tag(2, "B"),
+            entry_with_pid_tag(1, "A"),
+            entry_with_pid_tag(3, "C"),
+        ];
+        let mut state = State::new();
+        state.sort_column = Some(SortColumn::Pid);
+        state.sort_direction = SortDirection::Descending;
+        state.update(&entries);
+        assert_eq!(state.filtered_indices, vec![2, 0, 1]);
+    }
+
+    #[test]
+    fn sort_is_stable_for_equal_keys() {
+        let entries = vec![
+            entry_with_pid_tag(1, "first"),
+            entry_with_pid_tag(1, "second"),
+            entry_with_pid_tag(1, "third"),
+        ];
+        let mut state = State::new();
+        state.sort_column = Some(SortColumn::Pid);
+        state.update(&entries);
+        assert_eq!(state.filtered_indices, vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn record_search_moves_a_repeated_pattern_to_the_front_deduplicated() {
+        let mut state = State::new();
+        state.record_search("alpha".to_string());
+        state.record_search("beta".to_string());
+        state.record_search("alpha".to_string());
+        assert_eq!(state.search_history, vec!["alpha", "beta"]);
+    }
+
+    #[test]
+    fn record_search_caps_history_at_max_entries() {
+        let mut state = State::new();
+        for i in 0..MAX_SEARCH_HISTORY + 5 {
+            state.record_search(i.to_string());
+        }
+        assert_eq!(state.search_history.len(), MAX_SEARCH_HISTORY);
+        assert_eq!(
+            state.search_history[0],
+            (MAX_SEARCH_HISTORY + 4).to_string()
+        );
+    }
+}
@@ -0,0 +1,102 @@
+//! Per-tag entry counts for the `Alt+S` summary popup; see
+//! [`crate::app::App::open_tag_stats`]. Computed once when the popup opens
+//! rather than kept incrementally in sync, since it's a point-in-time
+//! "what's noisy right now" snapshot, not a live view.
+
+use std::collections::BTreeMap;
+
+use crate::log_entry::LogEntry;
+
+/// Number of [`LogLevel`] variants, the width of [`TagStat::by_level`].
+pub const LEVEL_COUNT: usize = 6;
+
+/// One row of the summary: a tag, its total entry count, and the
+/// breakdown by level, indexed by `level as usize`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TagStat {
+    pub tag: String,
+    pub total: usize,
+    pub by_level: [usize; LEVEL_COUNT],
+}
+
+/// Aggregate `entries` by tag, sorted by `total` descending (ties broken by
+/// tag name), so the noisiest tags sort to the top.
+pub fn compute(entries: &[LogEntry]) -> Vec<TagStat> {
+    let mut by_tag: BTreeMap<&str, [usize; LEVEL_COUNT]> = BTreeMap::new();
+    for entry in entries {
+        let counts = by_tag.entry(entry.tag.as_str()).or_default();
+        counts[entry.level as usize] += 1;
+    }
+
+    let mut stats: Vec<TagStat> = by_tag
+        .into_iter()
+        .map(|(tag, by_level)| TagStat {
+            tag: tag.to_string(),
+            total: by_level.iter().sum(),
+            by_level,
+        })
+        .collect();
+    stats.sort_by(|a, b| b.total.cmp(&a.total).then_with(|| a.tag.cmp(&b.tag)));
+    stats
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::log_entry::LogLevel;
+    use chrono::NaiveDateTime;
+
+    fn entry(tag: &str, level: LogLevel) -> LogEntry {
+        LogEntry {
+            timestamp: NaiveDateTime::default(),
+            pid: 0,
+            tid: 0,
+            level,
+            tag: tag.to_string(),
+            message: "msg".to_string(),
+            raw: "raw".to_string(),
+        }
+    }
+
+    #[test]
+    fn counts_entries_per_tag() {
+        let entries = vec![
+            entry("A", LogLevel::Info),
+            entry("A", LogLevel::Error),
+            entry("B", LogLevel::Info),
+        ];
+        let stats = compute(&entries);
+        assert_eq!(stats[0].tag, "A");
+        assert_eq!(stats[0].total, 2);
+        assert_eq!(stats[1].tag, "B");
+        assert_eq!(stats[1].total, 1);
+    }
+
+    #[test]
+    fn breaks_down_by_level() {
+        let entries = vec![
+            entry("A", LogLevel::Warn),
+            entry("A", LogLevel::Warn),
+            entry("A", LogLevel::Error),
+        ];
+        let stats = compute(&entries);
+        assert_eq!(stats[0].by_level[LogLevel::Warn as usize], 2);
+        assert_eq!(stats[0].by_level[LogLevel::Error as usize], 1);
+    }
+
+    #[test]
+    fn ties_broken_by_tag_name() {
+        let entries = vec![
+            entry("Zebra", LogLevel::Info),
+            entry("Apple", LogLevel::Info),
+        ];
+        let stats = compute(&entries);
+        assert_eq!(stats[0].tag, "Apple");
+        assert_eq!(stats[1].tag, "Zebra");
+    }
+
+    #[test]
+    fn empty_entries_produce_no_rows() {
+        assert!(compute(&[]).is_empty());
+    }
+}
@@ -0,0 +1,436 @@
+//! Central style definitions, kept together so a theme switch only has to
+//! touch this file. [`ThemeName::config`] maps a selectable theme to the
+//! concrete colors; [`init_theme`] latches the chosen one in at startup and
+//! [`level_style`]/[`dimmed_style`] read it back for rendering.
+
+use std::str::FromStr;
+use std::sync::OnceLock;
+
+use clap::ValueEnum;
+use ratatui::style::{Color, Modifier, Style};
+use serde::Deserialize;
+
+use crate::log_entry::LogLevel;
+
+/// The color for each thing the table styles: one per [`LogLevel`], plus the
+/// dimmed color used for non-matching rows in
+/// [`crate::filter::DisplayMode::Dim`].
+#[derive(Debug, Clone, Copy)]
+pub struct ThemeConfig {
+    pub verbose: Color,
+    pub debug: Color,
+    pub info: Color,
+    pub warn: Color,
+    pub error: Color,
+    pub fatal: Color,
+    pub dimmed: Color,
+    /// Status bar color for the active-filters indicator; see
+    /// [`filter_indicator_style`].
+    pub filter_indicator: Color,
+    /// Row background for even/odd display rows when zebra striping is on;
+    /// see [`crate::app::App::zebra_striping`] and [`zebra_style`]. Most
+    /// themes leave `zebra_even` at [`Color::Reset`] and only tint
+    /// `zebra_odd`, since striping is off until the user opts in and/or
+    /// overrides these in `config.toml`.
+    pub zebra_even: Color,
+    pub zebra_odd: Color,
+}
+
+/// A built-in, named color scheme. Select with `--theme` or `theme = "..."`
+/// in `config.toml`; see [`crate::config`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ThemeName {
+    #[default]
+    Default,
+    CatppuccinMocha,
+    Nord,
+    SolarizedDark,
+}
+
+/// Every built-in theme, in the order `--list-themes` prints them.
+pub const ALL_THEMES: [ThemeName; 4] = [
+    ThemeName::Default,
+    ThemeName::CatppuccinMocha,
+    ThemeName::Nord,
+    ThemeName::SolarizedDark,
+];
+
+impl ThemeName {
+    pub fn name(self) -> &'static str {
+        match self {
+            ThemeName::Default => "default",
+            ThemeName::CatppuccinMocha => "catppuccin-mocha",
+            ThemeName::Nord => "nord",
+            ThemeName::SolarizedDark => "solarized-dark",
+        }
+    }
+
+    pub fn config(self) -> ThemeConfig {
+        match self {
+            ThemeName::Default => default_theme(),
+            ThemeName::CatppuccinMocha => catppuccin_mocha(),
+            ThemeName::Nord => nord(),
+            ThemeName::SolarizedDark => solarized_dark(),
+        }
+    }
+}
+
+impl FromStr for ThemeName {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        ALL_THEMES
+            .into_iter()
+            .find(|theme| theme.name() == s)
+            .ok_or_else(|| format!("`{s}` is not a known theme"))
+    }
+}
+
+impl<'de> Deserialize<'de> for ThemeName {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(serde::de::Error::custom)
+    }
+}
+
+/// A single color read from the `[colors]` table of `config.toml`: anything
+/// [`Color`]'s `FromStr` accepts, i.e. a `#RRGGBB` hex code or a named color
+/// like `"lightblue"`. A thin wrapper because `ratatui`'s `Color` only
+/// derives `Deserialize` behind its own `serde` feature, which this crate
+/// doesn't enable.
+#[derive(Debug, Clone, Copy)]
+pub struct ConfigColor(pub Color);
+
+impl FromStr for ConfigColor {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.parse()
+            .map(ConfigColor)
+            .map_err(|_| format!("`{s}` is not a known color or #RRGGBB hex code"))
+    }
+}
+
+impl<'de> Deserialize<'de> for ConfigColor {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(serde::de::Error::custom)
+    }
+}
+
+/// Per-field color overrides from the `[colors]` table of `config.toml`,
+/// layered on top of whichever [`ThemeName`] is active; see
+/// [`ThemeConfig::with_overrides`]. Fields left unset keep the base theme's
+/// color.
+#[derive(Debug, Default, Clone, Deserialize)]
+pub struct ThemeOverrides {
+    pub verbose: Option<ConfigColor>,
+    pub debug: Option<ConfigColor>,
+    pub info: Option<ConfigColor>,
+    pub warn: Option<ConfigColor>,
+    pub error: Option<ConfigColor>,
+    pub fatal: Option<ConfigColor>,
+    pub dimmed: Option<ConfigColor>,
+    pub filter_indicator: Option<ConfigColor>,
+    pub zebra_even: Option<ConfigColor>,
+    pub zebra_odd: Option<ConfigColor>,
+}
+
+impl ThemeConfig {
+    /// Apply `overrides` on top of `self`, keeping `self`'s color for any
+    /// field left unset.
+    pub fn with_overrides(self, overrides: ThemeOverrides) -> ThemeConfig {
+        ThemeConfig {
+            verbose: overrides.verbose.map_or(self.verbose, |color| color.0),
+            debug: overrides.debug.map_or(self.debug, |color| color.0),
+            info: overrides.info.map_or(self.info, |color| color.0),
+            warn: overrides.warn.map_or(self.warn, |color| color.0),
+            error: overrides.error.map_or(self.error, |color| color.0),
+            fatal: overrides.fatal.map_or(self.fatal, |color| color.0),
+            dimmed: overrides.dimmed.map_or(self.dimmed, |color| color.0),
+            filter_indicator: overrides
+                .filter_indicator
+                .map_or(self.filter_indicator, |color| color.0),
+            zebra_even: overrides
+                .zebra_even
+                .map_or(self.zebra_even, |color| color.0),
+            zebra_odd: overrides.zebra_odd.map_or(self.zebra_odd, |color| color.0),
+        }
+    }
+}
+
+/// Matches the hardcoded styles this codebase shipped with before themes
+/// existed.
+fn default_theme() -> ThemeConfig {
+    ThemeConfig {
+        verbose: Color::Gray,
+        debug: Color::Cyan,
+        info: Color::Green,
+        warn: Color::Yellow,
+        error: Color::Red,
+        fatal: Color::Magenta,
+        dimmed: Color::DarkGray,
+        filter_indicator: Color::LightBlue,
+        zebra_even: Color::Reset,
+        zebra_odd: Color::Rgb(24, 24, 24),
+    }
+}
+
+fn catppuccin_mocha() -> ThemeConfig {
+    ThemeConfig {
+        verbose: Color::Rgb(147, 153, 178),          // Overlay2
+        debug: Color::Rgb(137, 220, 235),            // Sky
+        info: Color::Rgb(166, 227, 161),             // Green
+        warn: Color::Rgb(249, 226, 175),             // Yellow
+        error: Color::Rgb(243, 139, 168),            // Red
+        fatal: Color::Rgb(245, 194, 231),            // Pink
+        dimmed: Color::Rgb(88, 91, 112),             // Surface2
+        filter_indicator: Color::Rgb(203, 166, 247), // Mauve
+        zebra_even: Color::Reset,
+        zebra_odd: Color::Rgb(24, 24, 37), // Mantle
+    }
+}
+
+fn nord() -> ThemeConfig {
+    ThemeConfig {
+        verbose: Color::Rgb(216, 222, 233),          // nord4
+        debug: Color::Rgb(136, 192, 208),            // nord8
+        info: Color::Rgb(163, 190, 140),             // nord14
+        warn: Color::Rgb(235, 203, 139),             // nord13
+        error: Color::Rgb(191, 97, 106),             // nord11
+        fatal: Color::Rgb(180, 142, 173),            // nord15
+        dimmed: Color::Rgb(76, 86, 106),             // nord3
+        filter_indicator: Color::Rgb(129, 161, 193), // nord9
+        zebra_even: Color::Reset,
+        zebra_odd: Color::Rgb(59, 66, 82), // nord1
+    }
+}
+
+fn solarized_dark() -> ThemeConfig {
+    ThemeConfig {
+        verbose: Color::Rgb(101, 123, 131),         // base00
+        debug: Color::Rgb(42, 161, 152),            // cyan
+        info: Color::Rgb(133, 153, 0),              // green
+        warn: Color::Rgb(181, 137, 0),              // yellow
+        error: Color::Rgb(220, 50, 47),             // red
+        fatal: Color::Rgb(211, 54, 130),            // magenta
+        dimmed: Color::Rgb(88, 110, 117),           // base01
+        filter_indicator: Color::Rgb(38, 139, 210), // blue
+        zebra_even: Color::Reset,
+        zebra_odd: Color::Rgb(7, 54, 66), // base02
+    }
+}
+
+static THEME: OnceLock<ThemeConfig> = OnceLock::new();
+
+/// Latch in the theme to render with. Only the first call has any effect,
+/// and only if nothing has rendered yet: any rendering before it (e.g. in
+/// tests) latches in [`default_theme`], and later calls are ignored.
+pub fn init_theme(theme: ThemeConfig) {
+    let _ = THEME.set(theme);
+}
+
+fn theme() -> &'static ThemeConfig {
+    THEME.get_or_init(default_theme)
+}
+
+pub fn level_style(level: LogLevel) -> Style {
+    let theme = theme();
+    let color = match level {
+        LogLevel::Verbose => theme.verbose,
+        LogLevel::Debug => theme.debug,
+        LogLevel::Info => theme.info,
+        LogLevel::Warn => theme.warn,
+        LogLevel::Error => theme.error,
+        LogLevel::Fatal => theme.fatal,
+    };
+    Style::default().fg(color)
+}
+
+/// [`level_style`], but with [`Modifier::DIM`] added instead of full
+/// intensity — for [`crate::app::App::muted_level_color`], a quieter
+/// whole-row tint for sessions where the full-strength color on every line
+/// (message text included) reads as too loud. Same color, so it stays
+/// obviously tied to [`level_style`] at a glance.
+pub fn muted_level_style(level: LogLevel) -> Style {
+    level_style(level).add_modifier(Modifier::DIM)
+}
+
+/// Style for a row that's only shown for context in
+/// [`crate::filter::DisplayMode::Dim`] — present but not matching the
+/// active filter.
+pub fn dimmed_style() -> Style {
+    Style::default().fg(theme().dimmed)
+}
+
+/// Color for the active-filters segment of the status bar; see
+/// [`crate::app::App::filter_indicator`]. Exposed separately from
+/// [`filter_indicator_style`] so callers that need the raw color (e.g. to
+/// flash the whole status bar) don't have to unpack a [`Style`].
+pub fn filter_indicator_color() -> Color {
+    theme().filter_indicator
+}
+
+/// Style for the active-filters segment of the status bar; see
+/// [`crate::app::App::filter_indicator`].
+pub fn filter_indicator_style() -> Style {
+    Style::default().fg(filter_indicator_color())
+}
+
+/// Background for display row `index` when zebra striping is on; see
+/// [`crate::app::App::zebra_striping`]. `index` is the row's position within
+/// the currently displayed rows, not its source line number, so the stripe
+/// pattern doesn't shift as filters change which rows are shown.
+pub fn zebra_style(index: usize) -> Style {
+    let theme = theme();
+    let color = if index.is_multiple_of(2) {
+        theme.zebra_even
+    } else {
+        theme.zebra_odd
+    };
+    Style::default().bg(color)
+}
+
+/// Background for a quick-search match span other than the current one; see
+/// [`search_current_style`] and [`crate::display::DisplayData::as_row`].
+pub fn search_highlight_style() -> Style {
+    Style::default().bg(Color::Yellow)
+}
+
+/// Background for the current quick-search match span — the one `n`/`N`
+/// most recently landed on — so it stands out from any other matches on the
+/// same line; see [`crate::app::App::jump_to_match`] and
+/// [`search_highlight_style`] for the rest.
+pub fn search_current_style() -> Style {
+    Style::default().bg(Color::Magenta).fg(Color::Black)
+}
+
+/// Colors assigned, in order, to pinned highlights; see
+/// [`crate::app::App::pin_current_search`]. Deliberately fixed rather than
+/// theme-dependent, like [`search_highlight_style`] and
+/// [`search_current_style`] — yellow and magenta are already spoken for by
+/// those, so this palette avoids both.
+const PIN_PALETTE: [Color; 6] = [
+    Color::Green,
+    Color::Cyan,
+    Color::Blue,
+    Color::LightRed,
+    Color::LightMagenta,
+    Color::White,
+];
+
+/// Background for a pinned highlight's matches, cycling through
+/// [`PIN_PALETTE`] by `slot` (that pattern's position in
+/// [`crate::app::App::pinned_highlights`]) once there are more pinned
+/// patterns than colors.
+pub fn pin_highlight_style(slot: usize) -> Style {
+    Style::default()
+        .bg(PIN_PALETTE[slot % PIN_PALETTE.len()])
+        .fg(Color::Black)
+}
+
+/// Color for the `●` marker [`crate::display::DisplayData::as_row`] prefixes
+/// a bookmarked row's timestamp with; see
+/// [`crate::app::App::toggle_bookmark`].
+pub fn bookmark_style() -> Style {
+    Style::default()
+        .fg(Color::Cyan)
+        .add_modifier(Modifier::BOLD)
+}
+
+/// Style for the Tag cell of a row sharing the selected row's tag, when
+/// [`crate::app::App::toggle_tag_highlight`] is on. Deliberately fixed
+/// rather than theme-dependent, like [`search_highlight_style`], so it reads
+/// consistently across themes; bold rather than a background avoids
+/// competing with the search/pin highlight colors, which only ever paint the
+/// message column.
+pub fn tag_highlight_style() -> Style {
+    Style::default()
+        .fg(Color::Yellow)
+        .add_modifier(Modifier::BOLD)
+}
+
+/// Style for the `Alt+T` delta column's value when the gap since the
+/// previous visible entry is at least
+/// [`crate::app::App::delta_highlight_threshold`]. Deliberately fixed rather
+/// than theme-dependent, like [`tag_highlight_style`]; red rather than bold
+/// yellow keeps it visually distinct from [`tag_highlight_style`] while
+/// still reading as a flag rather than a [`LogLevel`] color.
+pub fn delta_highlight_style() -> Style {
+    Style::default().fg(Color::Red).add_modifier(Modifier::BOLD)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn theme_name_round_trips_through_its_display_string() {
+        for theme in ALL_THEMES {
+            assert_eq!(theme.name().parse::<ThemeName>().unwrap(), theme);
+        }
+    }
+
+    #[test]
+    fn rejects_unknown_theme_name() {
+        assert!("not-a-theme".parse::<ThemeName>().is_err());
+    }
+
+    #[test]
+    fn parses_hex_and_named_colors() {
+        assert_eq!(
+            "#ff0000".parse::<ConfigColor>().unwrap().0,
+            Color::Rgb(255, 0, 0)
+        );
+        assert_eq!(
+            "lightblue".parse::<ConfigColor>().unwrap().0,
+            Color::LightBlue
+        );
+    }
+
+    #[test]
+    fn rejects_unknown_color() {
+        assert!("not-a-color".parse::<ConfigColor>().is_err());
+    }
+
+    #[test]
+    fn zebra_style_alternates_by_row_position() {
+        let theme = default_theme();
+        assert_eq!(zebra_style(0).bg, Some(theme.zebra_even));
+        assert_eq!(zebra_style(1).bg, Some(theme.zebra_odd));
+        assert_eq!(zebra_style(2).bg, Some(theme.zebra_even));
+    }
+
+    #[test]
+    fn overrides_only_the_fields_that_are_set() {
+        let base = default_theme();
+        let overrides = ThemeOverrides {
+            error: Some(ConfigColor(Color::Rgb(1, 2, 3))),
+            ..Default::default()
+        };
+        let merged = base.with_overrides(overrides);
+        assert_eq!(merged.error, Color::Rgb(1, 2, 3));
+        assert_eq!(merged.info, base.info);
+    }
+
+    #[test]
+    fn default_theme_matches_the_original_hardcoded_colors() {
+        let theme = default_theme();
+        assert_eq!(theme.verbose, Color::Gray);
+        assert_eq!(theme.debug, Color::Cyan);
+        assert_eq!(theme.info, Color::Green);
+        assert_eq!(theme.warn, Color::Yellow);
+        assert_eq!(theme.error, Color::Red);
+        assert_eq!(theme.fatal, Color::Magenta);
+        assert_eq!(theme.dimmed, Color::DarkGray);
+        assert_eq!(theme.filter_indicator, Color::LightBlue);
+    }
+}
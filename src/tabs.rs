@@ -0,0 +1,75 @@
+//! Multiple open files, switched between with `Ctrl+Tab` / `Alt+1`..`Alt+9`.
+//! Each tab owns a completely independent [`App`] — its own entries,
+//! filters, search state, and selection — so switching tabs is just
+//! changing which one the event loop and renderer talk to; see
+//! [`Tabs::active`]/[`Tabs::active_mut`].
+
+use std::path::Path;
+
+use crate::app::App;
+
+/// At least one tab is always open; [`Tabs::new`] panics on an empty `Vec`
+/// rather than making every caller handle a state this app never actually
+/// reaches (there's always at least the one file/`--journald` stream passed
+/// on the command line).
+pub struct Tabs {
+    apps: Vec<App>,
+    active: usize,
+}
+
+impl Tabs {
+    pub fn new(apps: Vec<App>) -> Self {
+        assert!(!apps.is_empty(), "Tabs needs at least one App");
+        Self { apps, active: 0 }
+    }
+
+    pub fn active(&self) -> &App {
+        &self.apps[self.active]
+    }
+
+    pub fn active_mut(&mut self) -> &mut App {
+        &mut self.apps[self.active]
+    }
+
+    pub fn active_index(&self) -> usize {
+        self.active
+    }
+
+    pub fn len(&self) -> usize {
+        self.apps.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        false
+    }
+
+    /// File path (or `journalctl --follow ...` label) for each open tab, in
+    /// order, for [`crate::ui::draw_tabs`].
+    pub fn paths(&self) -> impl Iterator<Item = &Path> {
+        self.apps.iter().map(|app| app.path.as_path())
+    }
+
+    /// `Ctrl+Tab`: move to the next tab, wrapping back to the first past the
+    /// last.
+    pub fn activate_next(&mut self) {
+        self.select((self.active + 1) % self.apps.len());
+    }
+
+    /// `Alt+1`..`Alt+9`: jump straight to tab `index` (0-based); out of
+    /// range is a no-op rather than a panic, since it's driven directly by a
+    /// keypress that may not correspond to an open tab.
+    pub fn select(&mut self, index: usize) {
+        if index < self.apps.len() && index != self.active {
+            self.active = index;
+            self.active_mut().dirty = true;
+        }
+    }
+
+    /// Every open tab, for [`App::tick`]-style per-frame bookkeeping that
+    /// has to run on tabs other than the active one too (background
+    /// loaders/search workers keep making progress while their tab isn't
+    /// shown).
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut App> {
+        self.apps.iter_mut()
+    }
+}
@@ -0,0 +1,98 @@
+//! Small text-wrapping helpers shared by the table renderer.
+
+/// Split `s` into pieces at the given byte `indices`, returning the pieces
+/// in order. `indices` need not be sorted and may contain duplicates.
+/// Indices at or beyond `s.len()` (including on an empty string) are
+/// ignored rather than panicking.
+pub fn split_string_at_indices(s: &str, indices: &[usize]) -> Vec<String> {
+    let mut sorted: Vec<usize> = indices
+        .iter()
+        .copied()
+        .filter(|&i| i > 0 && i < s.len())
+        .collect();
+    sorted.sort_unstable();
+    sorted.dedup();
+
+    let mut pieces = Vec::with_capacity(sorted.len() + 1);
+    let mut start = 0;
+    for idx in sorted {
+        pieces.push(s[start..idx].to_string());
+        start = idx;
+    }
+    pieces.push(s[start..].to_string());
+    pieces
+}
+
+/// Compute the byte indices at which `s` should be wrapped so that no line
+/// exceeds `width` columns. Returns an empty vec if `s` already fits.
+pub fn wrap_indices(s: &str, width: usize) -> Vec<usize> {
+    if width == 0 || s.is_empty() {
+        return Vec::new();
+    }
+
+    let mut indices = Vec::new();
+    for (col, (byte_idx, _ch)) in s.char_indices().enumerate() {
+        if col > 0 && col % width == 0 {
+            indices.push(byte_idx);
+        }
+    }
+    indices
+}
+
+/// Build the wrapped lines of `s` for a column of the given `width`.
+pub fn create_text(s: &str, width: usize) -> Vec<String> {
+    let indices = wrap_indices(s, width);
+    if indices.is_empty() {
+        return vec![s.to_string()];
+    }
+    split_string_at_indices(s, &indices)
+}
+
+/// Truncate `s` to at most `width` characters, for a collapsed row that
+/// should render as a single line instead of wrapping.
+pub fn truncate_to_width(s: &str, width: usize) -> String {
+    s.chars().take(width).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wraps_long_line() {
+        let lines = create_text("abcdefghij", 4);
+        assert_eq!(lines, vec!["abcd", "efgh", "ij"]);
+    }
+
+    #[test]
+    fn short_line_is_untouched() {
+        let lines = create_text("abc", 10);
+        assert_eq!(lines, vec!["abc"]);
+    }
+
+    #[test]
+    fn empty_string_does_not_panic() {
+        assert_eq!(create_text("", 10), vec![""]);
+        assert_eq!(split_string_at_indices("", &[0]), vec![""]);
+    }
+
+    #[test]
+    fn whitespace_only_does_not_panic() {
+        assert_eq!(create_text(" ", 10), vec![" "]);
+    }
+
+    #[test]
+    fn single_character_does_not_panic() {
+        assert_eq!(create_text("a", 1), vec!["a"]);
+    }
+
+    #[test]
+    fn truncates_to_width() {
+        assert_eq!(truncate_to_width("abcdefgh", 4), "abcd");
+    }
+
+    #[test]
+    fn truncate_leaves_short_strings_untouched() {
+        assert_eq!(truncate_to_width("abc", 10), "abc");
+    }
+}
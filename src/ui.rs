@@ -0,0 +1,765 @@
+use ratatui::layout::{Constraint, Direction, Layout, Rect};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{
+    Block, Borders, Clear, List, ListItem, Paragraph, Row, Scrollbar, ScrollbarOrientation,
+    ScrollbarState, Sparkline, Table, Tabs as TabBar,
+};
+use ratatui::Frame;
+
+use crate::app::{App, Mode};
+use crate::config::{ColumnWidth, ColumnWidthConfig};
+use crate::filter::DisplayMode;
+use crate::log_entry::{LogEntry, LogLevel};
+
+/// Built-in column widths, used for any column without an override.
+const DEFAULT_TAG_WIDTH: u16 = 18;
+/// Clamp for [`ColumnWidths::adjust_tag_width`], so `<`/`>` can't shrink the
+/// Tag column to nothing or grow it past anything a terminal could usefully
+/// show.
+const MIN_TAG_WIDTH: u16 = 1;
+const MAX_TAG_WIDTH: u16 = 60;
+const DEFAULT_PID_WIDTH: u16 = 6;
+/// `MM-DD HH:MM:SS` plus up to nanosecond-precision fractional seconds.
+const DEFAULT_TIMESTAMP_WIDTH: u16 = 24;
+pub(crate) const TID_COLUMN_WIDTH: u16 = 6;
+/// Width of the single-character level column, shared with `app::column_at`.
+pub(crate) const LEVEL_COLUMN_WIDTH: u16 = 1;
+/// Width of the optional `Alt+T` inter-line time delta column, wide enough
+/// for `"+1234.567s"`; shared with `app::column_at`.
+pub(crate) const DELTA_COLUMN_WIDTH: u16 = 10;
+
+/// Resolved `tag`/`pid`/`timestamp` column widths, computed once from a
+/// [`ColumnWidthConfig`] and the loaded entries (`auto` sizes to the widest
+/// value actually present; unset keeps the built-in default).
+#[derive(Debug, Clone, Copy)]
+pub struct ColumnWidths {
+    pub tag: u16,
+    pub pid: u16,
+    pub timestamp: u16,
+}
+
+impl ColumnWidths {
+    pub fn resolve(config: ColumnWidthConfig, entries: &[LogEntry]) -> Self {
+        ColumnWidths {
+            tag: resolve_width(config.max_tag_width, DEFAULT_TAG_WIDTH, || {
+                entries.iter().map(|e| e.tag.len() as u16).max()
+            }),
+            pid: resolve_width(config.max_pid_width, DEFAULT_PID_WIDTH, || {
+                entries.iter().map(|e| e.pid.to_string().len() as u16).max()
+            }),
+            timestamp: resolve_width(config.max_timestamp_width, DEFAULT_TIMESTAMP_WIDTH, || {
+                entries
+                    .iter()
+                    .map(|e| e.timestamp.format("%m-%d %H:%M:%S%.f").to_string().len() as u16)
+                    .max()
+            }),
+        }
+    }
+
+    fn fixed_columns_width(self) -> u16 {
+        self.timestamp + self.pid + TID_COLUMN_WIDTH + LEVEL_COLUMN_WIDTH + self.tag
+    }
+
+    /// `<`/`>`/`Alt+Left`/`Alt+Right`: grow or shrink the Tag column by one
+    /// column, clamped to [`MIN_TAG_WIDTH`]..=[`MAX_TAG_WIDTH`]. The Message
+    /// column has no explicit width of its own — it takes whatever's left of
+    /// the terminal after [`fixed_columns_width`] — so this is the only
+    /// adjustment needed to trade space between the two at runtime. See
+    /// [`crate::app::App::adjust_tag_width`].
+    pub fn adjust_tag_width(&mut self, delta: i16) {
+        self.tag = self
+            .tag
+            .saturating_add_signed(delta)
+            .clamp(MIN_TAG_WIDTH, MAX_TAG_WIDTH);
+    }
+}
+
+/// Truncates `label` to `width` columns with a trailing `…` when it doesn't
+/// fit, instead of letting ratatui silently clip it mid-character or panic.
+/// Only the Tag header currently needs this, since its width is the only one
+/// adjustable at runtime (see [`ColumnWidths::adjust_tag_width`]) down to a
+/// single column.
+fn header_label(label: &str, width: u16) -> String {
+    let width = width as usize;
+    if label.chars().count() <= width {
+        return label.to_string();
+    }
+    if width == 0 {
+        return String::new();
+    }
+    if width == 1 {
+        return "…".to_string();
+    }
+    let truncated: String = label.chars().take(width - 1).collect();
+    format!("{truncated}…")
+}
+
+fn resolve_width(
+    override_width: Option<ColumnWidth>,
+    default: u16,
+    widest_loaded: impl FnOnce() -> Option<u16>,
+) -> u16 {
+    match override_width {
+        None => default,
+        Some(ColumnWidth::Fixed(width)) => width,
+        Some(ColumnWidth::Auto) => widest_loaded().unwrap_or(default),
+    }
+}
+
+/// `tab_titles`/`active_tab` come from [`crate::tabs::Tabs`]; the tab bar
+/// itself is only rendered when more than one tab is open, so a
+/// single-file session looks exactly like it did before tabs existed.
+pub fn draw(frame: &mut Frame, app: &mut App, tab_titles: &[String], active_tab: usize) {
+    let show_tabs = tab_titles.len() > 1;
+    let constraints = if show_tabs {
+        vec![
+            Constraint::Length(1),
+            Constraint::Min(1),
+            Constraint::Length(1),
+        ]
+    } else {
+        vec![Constraint::Min(1), Constraint::Length(1)]
+    };
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints(constraints)
+        .split(frame.size());
+
+    let (table_area, status_area) = if show_tabs {
+        draw_tabs(frame, tab_titles, active_tab, chunks[0]);
+        (chunks[1], chunks[2])
+    } else {
+        (chunks[0], chunks[1])
+    };
+
+    draw_table(frame, app, table_area);
+    draw_status_bar(frame, app, status_area);
+
+    if app.mode == Mode::PresetPicker {
+        draw_preset_picker(frame, app, table_area);
+    }
+    if app.mode == Mode::TagStats {
+        draw_tag_stats(frame, app, table_area);
+    }
+    if app.mode == Mode::Histogram {
+        draw_histogram(frame, app, table_area);
+    }
+    if app.mode == Mode::PinnedHighlights {
+        draw_pinned_highlights(frame, app, table_area);
+    }
+    if app.mode == Mode::Bookmarks {
+        draw_bookmarks(frame, app, table_area);
+    }
+    if app.mode == Mode::EntryDetail {
+        draw_entry_detail(frame, app);
+    }
+}
+
+/// One-line bar of open file paths, the active tab reverse-videoed, shown
+/// above the table only when [`crate::tabs::Tabs::len`] is more than one.
+fn draw_tabs(frame: &mut Frame, titles: &[String], active: usize, area: Rect) {
+    let tabs = TabBar::new(
+        titles
+            .iter()
+            .map(|title| Line::raw(title.clone()))
+            .collect::<Vec<_>>(),
+    )
+    .select(active)
+    .highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+    frame.render_widget(tabs, area);
+}
+
+/// Popup opened with `F`, listing `app.presets` by name with the currently
+/// highlighted one reverse-videoed. Centered over the table area.
+fn draw_preset_picker(frame: &mut Frame, app: &App, area: Rect) {
+    let height = (app.presets.len() as u16 + 2).min(area.height);
+    let width = area.width.min(60);
+    let popup = Rect {
+        x: area.x + (area.width.saturating_sub(width)) / 2,
+        y: area.y + (area.height.saturating_sub(height)) / 2,
+        width,
+        height,
+    };
+
+    let selected = app.preset_picker_selected();
+    let items: Vec<ListItem> = app
+        .presets
+        .iter()
+        .enumerate()
+        .map(|(index, preset)| {
+            let line = format!("{}  {}", preset.name, preset.expression);
+            let style = if index == selected {
+                Style::default().add_modifier(Modifier::REVERSED)
+            } else {
+                Style::default()
+            };
+            ListItem::new(line).style(style)
+        })
+        .collect();
+
+    let list = List::new(items).block(Block::default().borders(Borders::ALL).title("Presets"));
+
+    frame.render_widget(Clear, popup);
+    frame.render_widget(list, popup);
+}
+
+/// `Alt+S` popup, listing [`App::tag_stats`] noisiest-tag-first with a
+/// per-level breakdown, the highlighted row reverse-videoed; `Enter` there
+/// filters the table down to it. Centered over the table area like
+/// [`draw_preset_picker`].
+fn draw_tag_stats(frame: &mut Frame, app: &App, area: Rect) {
+    let stats = app.tag_stats();
+    let height = (stats.len() as u16 + 2).min(area.height);
+    let width = area.width.min(70);
+    let popup = Rect {
+        x: area.x + (area.width.saturating_sub(width)) / 2,
+        y: area.y + (area.height.saturating_sub(height)) / 2,
+        width,
+        height,
+    };
+
+    const LEVELS: [LogLevel; 6] = [
+        LogLevel::Verbose,
+        LogLevel::Debug,
+        LogLevel::Info,
+        LogLevel::Warn,
+        LogLevel::Error,
+        LogLevel::Fatal,
+    ];
+
+    let selected = app.tag_stats_selected();
+    let items: Vec<ListItem> = stats
+        .iter()
+        .enumerate()
+        .map(|(index, stat)| {
+            let breakdown = LEVELS
+                .iter()
+                .map(|&level| format!("{}:{}", level.as_char(), stat.by_level[level as usize]))
+                .collect::<Vec<_>>()
+                .join(" ");
+            let line = format!("{:<18} {:>6}  {breakdown}", stat.tag, stat.total);
+            let style = if index == selected {
+                Style::default().add_modifier(Modifier::REVERSED)
+            } else {
+                Style::default()
+            };
+            ListItem::new(line).style(style)
+        })
+        .collect();
+
+    let list = List::new(items).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title("Tags (Enter to filter)"),
+    );
+
+    frame.render_widget(Clear, popup);
+    frame.render_widget(list, popup);
+}
+
+/// `Alt+H` popup: a [`Sparkline`] of [`App::histogram_buckets`] entry counts
+/// over time, a `^` marking [`App::histogram_selected`] underneath it, and a
+/// line naming that bucket's time window and count. `Left`/`Right` move the
+/// marker; `Enter` jumps the table to the bucket's earliest entry.
+fn draw_histogram(frame: &mut Frame, app: &App, area: Rect) {
+    let buckets = app.histogram_buckets();
+    let width = (buckets.len() as u16 + 4).max(30).min(area.width.max(1));
+    let height = 5.min(area.height);
+    let popup = Rect {
+        x: area.x + (area.width.saturating_sub(width)) / 2,
+        y: area.y + (area.height.saturating_sub(height)) / 2,
+        width,
+        height,
+    };
+
+    frame.render_widget(Clear, popup);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title("Entries over time (Enter to jump)");
+    let inner = block.inner(popup);
+    frame.render_widget(block, popup);
+
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Min(1),
+            Constraint::Length(1),
+            Constraint::Length(1),
+        ])
+        .split(inner);
+
+    let data: Vec<u64> = buckets.iter().map(|bucket| bucket.count as u64).collect();
+    frame.render_widget(Sparkline::default().data(&data), rows[0]);
+
+    let selected = app.histogram_selected();
+    let marker_column = selected.min(rows[1].width.saturating_sub(1) as usize);
+    frame.render_widget(
+        Paragraph::new(Line::raw(format!("{}^", " ".repeat(marker_column)))),
+        rows[1],
+    );
+
+    let detail = match buckets.get(selected) {
+        Some(bucket) => format!(
+            "{}  {} entr{}",
+            bucket.start.format("%m-%d %H:%M:%S"),
+            bucket.count,
+            if bucket.count == 1 { "y" } else { "ies" }
+        ),
+        None => "No entries loaded".to_string(),
+    };
+    frame.render_widget(Paragraph::new(Line::raw(detail)), rows[2]);
+}
+
+/// `Alt+P` popup, listing [`App::pinned_highlights`] each in its own
+/// [`crate::styles::pin_highlight_style`] color, the highlighted one
+/// reverse-videoed; `Enter`/`Delete`/`d` there unpins it. Centered over the
+/// table area like [`draw_preset_picker`].
+fn draw_pinned_highlights(frame: &mut Frame, app: &App, area: Rect) {
+    let pinned = app.pinned_highlights();
+    let height = (pinned.len() as u16 + 2).min(area.height);
+    let width = area.width.min(60);
+    let popup = Rect {
+        x: area.x + (area.width.saturating_sub(width)) / 2,
+        y: area.y + (area.height.saturating_sub(height)) / 2,
+        width,
+        height,
+    };
+
+    let selected = app.pinned_selected();
+    let items: Vec<ListItem> = pinned
+        .iter()
+        .enumerate()
+        .map(|(slot, pattern)| {
+            let mut style = crate::styles::pin_highlight_style(slot);
+            if slot == selected {
+                style = style.add_modifier(Modifier::REVERSED);
+            }
+            ListItem::new(pattern.as_str()).style(style)
+        })
+        .collect();
+
+    let list = List::new(items).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title("Pinned highlights (Enter/d to unpin)"),
+    );
+
+    frame.render_widget(Clear, popup);
+    frame.render_widget(list, popup);
+}
+
+/// `M` popup, listing [`App::bookmarks`] with their timestamp/tag/message,
+/// the highlighted one reverse-videoed; `Enter` jumps to it, `Delete`/`d`
+/// removes it. Centered over the table area like [`draw_preset_picker`].
+fn draw_bookmarks(frame: &mut Frame, app: &App, area: Rect) {
+    let bookmarks = app.bookmarks();
+    let height = (bookmarks.len() as u16 + 2).min(area.height);
+    let width = area.width.min(90);
+    let popup = Rect {
+        x: area.x + (area.width.saturating_sub(width)) / 2,
+        y: area.y + (area.height.saturating_sub(height)) / 2,
+        width,
+        height,
+    };
+
+    let selected = app.bookmark_selected();
+    let items: Vec<ListItem> = bookmarks
+        .iter()
+        .enumerate()
+        .map(|(index, (entry, _))| {
+            let line = format!(
+                "{}  {:<18} {}",
+                entry.timestamp.format("%m-%d %H:%M:%S%.f"),
+                entry.tag,
+                entry.message
+            );
+            let style = if index == selected {
+                Style::default().add_modifier(Modifier::REVERSED)
+            } else {
+                Style::default()
+            };
+            ListItem::new(line).style(style)
+        })
+        .collect();
+
+    let list = List::new(items).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title("Bookmarks (Enter to jump, d to remove)"),
+    );
+
+    frame.render_widget(Clear, popup);
+    frame.render_widget(list, popup);
+}
+
+/// `Space`/`o` popup for the selected row: every field of the `LogEntry` on
+/// its own line, full-precision timestamp and spelled-out level included,
+/// then the message word-wrapped to the popup width with `Up`/`Down`
+/// scrolling if it's taller than the popup; see
+/// [`crate::app::App::detail_popup_rect`]/[`crate::app::App::entry_detail_input`].
+/// Quick-search matches inside the message are highlighted the same as in
+/// the table, via [`crate::app::App::detail_message_search_spans`].
+fn draw_entry_detail(frame: &mut Frame, app: &App) {
+    let Some(entry) = app.selected_entry() else {
+        return;
+    };
+    let popup = app.detail_popup_rect();
+    frame.render_widget(Clear, popup);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title("Detail (Esc/Space/o to close)");
+    let inner = block.inner(popup);
+    frame.render_widget(block, popup);
+
+    let header = vec![
+        Line::raw(format!(
+            "Timestamp: {}",
+            entry.timestamp.format("%Y-%m-%d %H:%M:%S%.9f")
+        )),
+        Line::raw(format!("PID: {}", entry.pid)),
+        Line::raw(format!("TID: {}", entry.tid)),
+        Line::raw(format!("Level: {}", entry.level.name())),
+        Line::raw(format!("Tag: {}", entry.tag)),
+        Line::raw(""),
+    ];
+    let header_height = header.len() as u16;
+    let header_area = Rect {
+        height: header_height.min(inner.height),
+        ..inner
+    };
+    frame.render_widget(Paragraph::new(header), header_area);
+
+    let message_area = Rect {
+        y: inner.y + header_height,
+        height: inner.height.saturating_sub(header_height),
+        ..inner
+    };
+    let message_lines = app.detail_message_lines();
+    let search_spans = app.detail_message_search_spans();
+    let mut offset = 0;
+    let styled_lines: Vec<Line> = message_lines
+        .iter()
+        .map(|line| {
+            let start = offset;
+            offset += line.len();
+            highlighted_message_line(line, start, &search_spans)
+        })
+        .collect();
+
+    let paragraph = Paragraph::new(styled_lines).scroll((app.detail_scroll(), 0));
+    frame.render_widget(paragraph, message_area);
+}
+
+/// Split `line` (the slice of the full message starting at `line_start`)
+/// into spans, highlighting the parts overlapping `search_spans` (byte
+/// ranges into the full message) with [`crate::styles::search_highlight_style`].
+/// A simpler cousin of [`crate::display::DisplayData::as_row`]'s span
+/// overlay: the detail popup only ever needs to highlight search matches,
+/// not level placeholders or pinned highlights.
+fn highlighted_message_line(
+    line: &str,
+    line_start: usize,
+    search_spans: &[(usize, usize)],
+) -> Line<'static> {
+    let line_end = line_start + line.len();
+    let mut boundaries: Vec<usize> = vec![0, line.len()];
+    for &(start, end) in search_spans {
+        boundaries.push(
+            start
+                .max(line_start)
+                .min(line_end)
+                .saturating_sub(line_start),
+        );
+        boundaries.push(end.max(line_start).min(line_end).saturating_sub(line_start));
+    }
+    boundaries.sort_unstable();
+    boundaries.dedup();
+
+    boundaries
+        .windows(2)
+        .filter(|pair| pair[0] < pair[1])
+        .map(|pair| {
+            let (start, end) = (pair[0], pair[1]);
+            let highlighted = search_spans.iter().any(|&(span_start, span_end)| {
+                let span_start = span_start
+                    .max(line_start)
+                    .min(line_end)
+                    .saturating_sub(line_start);
+                let span_end = span_end
+                    .max(line_start)
+                    .min(line_end)
+                    .saturating_sub(line_start);
+                span_start <= start && end <= span_end && span_start < span_end
+            });
+            let style = if highlighted {
+                crate::styles::search_highlight_style()
+            } else {
+                Style::default()
+            };
+            Span::styled(line[start..end].to_string(), style)
+        })
+        .collect()
+}
+
+fn draw_table(frame: &mut Frame, app: &mut App, area: Rect) {
+    if app.show_raw {
+        app.table_area = area;
+        draw_raw_table(frame, app, area);
+        return;
+    }
+
+    let (table_area, scrollbar_area) = if area.width > 1 {
+        let chunks = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Min(1), Constraint::Length(1)])
+            .split(area);
+        (chunks[0], Some(chunks[1]))
+    } else {
+        (area, None)
+    };
+    app.table_area = table_area;
+
+    let widths = app.column_widths;
+    let tag_width = widths.tag as usize;
+    let show_delta = app.show_delta_column;
+    let delta_width = if show_delta { DELTA_COLUMN_WIDTH } else { 0 };
+    let message_width = table_area
+        .width
+        .saturating_sub(widths.fixed_columns_width())
+        .saturating_sub(delta_width)
+        .max(1) as usize;
+    let rows = app.visible_rows(message_width, tag_width);
+
+    let mut constraints = vec![Constraint::Length(widths.timestamp)];
+    let mut header = vec![header_label("Time", widths.timestamp)];
+    if show_delta {
+        constraints.push(Constraint::Length(DELTA_COLUMN_WIDTH));
+        header.push(header_label("Δ", DELTA_COLUMN_WIDTH));
+    }
+    constraints.extend([
+        Constraint::Length(widths.pid),
+        Constraint::Length(TID_COLUMN_WIDTH),
+        Constraint::Length(LEVEL_COLUMN_WIDTH),
+        Constraint::Length(widths.tag),
+        Constraint::Min(10),
+    ]);
+    header.extend([
+        header_label("PID", widths.pid),
+        header_label("TID", TID_COLUMN_WIDTH),
+        header_label("L", LEVEL_COLUMN_WIDTH),
+        header_label("Tag", widths.tag),
+        "Message".to_string(),
+    ]);
+
+    let table = Table::new(rows, constraints)
+        .header(Row::new(header))
+        .highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+
+    frame.render_stateful_widget(table, table_area, &mut app.table.state);
+
+    if let Some(scrollbar_area) = scrollbar_area {
+        draw_scrollbar(frame, app, scrollbar_area);
+    }
+}
+
+/// Right-edge scrollbar for the table: a thumb showing where the selected
+/// row sits among [`crate::display::DisplayData::rows`], plus tick marks in
+/// [`crate::app::App::match_row_indices`]/[`crate::app::App::bookmark_row_indices`]'s
+/// colors for quick-search matches and bookmarks, so both are visible at a
+/// glance in a file too long to scroll through looking for them. The table
+/// header takes up the first row of `area`, so the track starts below it,
+/// same as the table body.
+fn draw_scrollbar(frame: &mut Frame, app: &App, area: Rect) {
+    let len = app.display.rows.len();
+    let track = Rect {
+        y: area.y + 1,
+        height: area.height.saturating_sub(1),
+        ..area
+    };
+    if len == 0 || track.height == 0 {
+        return;
+    }
+
+    let mut state = ScrollbarState::new(len).position(app.table.selected().unwrap_or(0));
+    let scrollbar = Scrollbar::new(ScrollbarOrientation::VerticalRight)
+        .begin_symbol(None)
+        .end_symbol(None)
+        .track_symbol(Some("│"))
+        .thumb_symbol("█");
+    frame.render_stateful_widget(scrollbar, track, &mut state);
+
+    let track_row = |index: usize| -> u16 {
+        if len <= 1 {
+            track.y
+        } else {
+            track.y + ((index * (track.height as usize - 1)) / (len - 1)) as u16
+        }
+    };
+    let buffer = frame.buffer_mut();
+    for index in app.match_row_indices() {
+        buffer
+            .get_mut(track.x, track_row(index))
+            .set_char('▸')
+            .set_style(Style::default().fg(Color::Yellow));
+    }
+    for index in app.bookmark_row_indices() {
+        buffer
+            .get_mut(track.x, track_row(index))
+            .set_char('●')
+            .set_style(crate::styles::bookmark_style());
+    }
+}
+
+/// `Alt+R` view: each row is the original unparsed line in a single column,
+/// like `less`, instead of the usual per-field layout.
+fn draw_raw_table(frame: &mut Frame, app: &mut App, area: Rect) {
+    let rows: Vec<Row> = app
+        .display
+        .rows
+        .iter()
+        .enumerate()
+        .map(|(index, entry)| {
+            let style = if app.display.dimmed.get(index).copied().unwrap_or(false) {
+                crate::styles::dimmed_style()
+            } else {
+                Style::default()
+            };
+            Row::new(vec![Line::raw(entry.raw.clone())]).style(style)
+        })
+        .collect();
+
+    let table = Table::new(rows, [Constraint::Min(10)]).header(Row::new(vec!["Raw"]));
+
+    frame.render_stateful_widget(table, area, &mut app.table.state);
+}
+
+fn draw_status_bar(frame: &mut Frame, app: &App, area: Rect) {
+    if let Some(prompt) = input_prompt(app) {
+        frame.render_widget(Paragraph::new(Line::raw(prompt)), area);
+        if let Some(column) = input_cursor_column(app) {
+            frame.set_cursor(area.x + column, area.y);
+        }
+        return;
+    }
+
+    let since_last_same_tag = match app.time_since_last_same_tag() {
+        Some(delta) => format!(
+            "+{:.3}s since last {}",
+            delta.num_milliseconds() as f64 / 1000.0,
+            app.selected_entry().map(|e| e.tag.as_str()).unwrap_or("")
+        ),
+        None => "no earlier entry with this tag".to_string(),
+    };
+    let visible = app.visible_indices().len();
+    let match_suffix = match app.display_mode() {
+        DisplayMode::Dim => format!(" ({} match)", app.matching_row_count()),
+        DisplayMode::Hide => String::new(),
+    };
+    let row = match app.table.selected() {
+        Some(index) => format!("Row {}/{visible}{match_suffix}", index + 1),
+        None => format!("Row -/{visible}{match_suffix}"),
+    };
+    let row = format!("{row} — {}", app.selected_timestamp());
+    let mut spans = vec![Span::raw(format!(
+        "{} — {row} — {} — {}",
+        app.path.display(),
+        since_last_same_tag,
+        app.status
+    ))];
+    if let Some(indicator) = app.filter_indicator() {
+        spans.push(Span::raw(" — "));
+        spans.push(Span::styled(
+            indicator,
+            crate::styles::filter_indicator_style(),
+        ));
+    }
+    if app.new_lines_below() > 0 {
+        spans.push(Span::raw(" — "));
+        spans.push(Span::styled(
+            format!("{} new lines below", app.new_lines_below()),
+            crate::styles::filter_indicator_style(),
+        ));
+    }
+    if let Some(label) = app.display_tz_label() {
+        spans.push(Span::raw(format!(" — {label}")));
+    }
+    if !app.pinned_highlights().is_empty() {
+        spans.push(Span::raw(" — Pinned: "));
+        for (slot, pattern) in app.pinned_highlights().iter().enumerate() {
+            if slot > 0 {
+                spans.push(Span::raw(", "));
+            }
+            spans.push(Span::styled(
+                pattern.clone(),
+                crate::styles::pin_highlight_style(slot),
+            ));
+        }
+    }
+    if app.debug {
+        spans.push(Span::raw(format!(" — {:.1} fps", app.fps())));
+    }
+    let text = Line::from(spans);
+    let paragraph = match app.active_flash_color() {
+        Some(color) => Paragraph::new(text).style(Style::default().bg(color)),
+        None => Paragraph::new(text),
+    };
+    frame.render_widget(paragraph, area);
+}
+
+/// The line to show in place of the status bar while typing a command or a
+/// live tag filter, or `None` in normal mode.
+fn input_prompt(app: &App) -> Option<String> {
+    match app.mode {
+        Mode::Command => Some(format!(":{}", app.input_buffer)),
+        Mode::TagFilter => Some(format!("Filter tag: {}", app.input_buffer)),
+        Mode::FilterExpr => Some(match &app.filter_expr_error {
+            Some(err) => format!("Filter: {} — {err}", app.input_buffer),
+            None => format!("Filter: {}", app.input_buffer),
+        }),
+        Mode::QuickSearch => Some(format!(
+            "{}{}",
+            if app.state.fuzzy { "/~" } else { "/" },
+            app.input_buffer
+        )),
+        Mode::QuickFilter => Some("Quick filter: [P]ID [T]ag [L]evel [M]essage".to_string()),
+        Mode::PresetPicker
+        | Mode::TagStats
+        | Mode::Histogram
+        | Mode::PinnedHighlights
+        | Mode::Bookmarks
+        | Mode::EntryDetail
+        | Mode::Normal => None,
+    }
+}
+
+/// Screen column of the edit cursor within the prompt line rendered by
+/// [`input_prompt`], for the modes backed by an editable `app.input_buffer`.
+/// Columns are counted in chars, like [`crate::text_utils::truncate_to_width`]
+/// elsewhere in the table rendering, not true terminal display width.
+fn input_cursor_column(app: &App) -> Option<u16> {
+    let prefix_width = match app.mode {
+        Mode::Command => 1, // ":"
+        Mode::TagFilter => "Filter tag: ".chars().count(),
+        Mode::FilterExpr => "Filter: ".chars().count(),
+        Mode::QuickSearch => {
+            if app.state.fuzzy {
+                2 // "/~"
+            } else {
+                1 // "/"
+            }
+        }
+        Mode::QuickFilter
+        | Mode::PresetPicker
+        | Mode::TagStats
+        | Mode::Histogram
+        | Mode::PinnedHighlights
+        | Mode::Bookmarks
+        | Mode::EntryDetail
+        | Mode::Normal => return None,
+    };
+    let cursor_width = app.input_buffer[..app.input_cursor].chars().count();
+    Some((prefix_width + cursor_width) as u16)
+}